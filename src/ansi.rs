@@ -0,0 +1,265 @@
+use std::fmt::Write;
+use crate::component::{BaseComponent, Component, TextComponent};
+use crate::formatting::{Color, DefaultColor, HexColor};
+
+/// SGR foreground code of the closest standard terminal color
+const fn default_color_code(color: DefaultColor) -> u8 {
+    match color {
+        DefaultColor::Black => 30,
+        DefaultColor::DarkRed => 31,
+        DefaultColor::DarkGreen => 32,
+        DefaultColor::Gold => 33,
+        DefaultColor::DarkBlue => 34,
+        DefaultColor::Purple => 35,
+        DefaultColor::DarkCyan => 36,
+        DefaultColor::Gray => 37,
+        DefaultColor::DarkGray => 90,
+        DefaultColor::Red => 91,
+        DefaultColor::BrightGreen => 92,
+        DefaultColor::Yellow => 93,
+        DefaultColor::Blue => 94,
+        DefaultColor::Pink => 95,
+        DefaultColor::Cyan => 96,
+        DefaultColor::White => 97,
+    }
+}
+
+/// Renders the component as text with SGR escape sequences. Every styled run is reset at its
+/// end and styles are inherited like in vanilla. Default colors use the 16 standard terminal
+/// colors and hex colors use 24-bit sequences. Obfuscated text has no terminal equivalent and
+/// is rendered as is
+pub fn to_ansi(component: &Component) -> String {
+    let mut ansi = String::new();
+    component.walk_styled(&BaseComponent::root_style(), &mut |component, style| {
+        let content = component.content();
+        if content.is_empty() {
+            return;
+        }
+        let mut codes = Vec::new();
+        for (enabled, code) in [
+            (style.bold, "1"),
+            (style.italic, "3"),
+            (style.underlined, "4"),
+            (style.strikethrough, "9"),
+        ] {
+            if enabled == Some(true) {
+                codes.push(code.to_string());
+            }
+        }
+        match &style.color {
+            Some(Color::Default(color)) => codes.push(default_color_code(*color).to_string()),
+            Some(Color::Hex(color)) => {
+                let (r, g, b) = color.get_rgb();
+                codes.push(format!("38;2;{};{};{}", r, g, b));
+            }
+            None => {}
+        }
+        match codes.is_empty() {
+            true => ansi.push_str(&content),
+            false => {
+                let _ = write!(ansi, "\x1b[{}m{}\x1b[0m", codes.join(";"), content);
+            }
+        }
+    });
+    ansi
+}
+
+/// Default color rendered with the given SGR foreground code, the inverse of [`to_ansi`]
+fn color_from_code(code: u8) -> Option<DefaultColor> {
+    DefaultColor::ALL.into_iter().find(|color| default_color_code(*color) == code)
+}
+
+/// Color of an entry in the 256 color palette. The 16 standard colors map to default colors
+fn palette_color(index: u8) -> Color<'static> {
+    match index {
+        0..=7 => color_from_code(30 + index).expect("standard colors are mapped").into(),
+        8..=15 => color_from_code(90 + index - 8).expect("bright colors are mapped").into(),
+        16..=231 => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + 40 * value };
+            let index = index - 16;
+            HexColor::new_rgb(level(index / 36), level(index / 6 % 6), level(index % 6)).into()
+        }
+        232..=255 => {
+            let gray = 8 + 10 * (index - 232);
+            HexColor::new_rgb(gray, gray, gray).into()
+        }
+    }
+}
+
+/// Applies the parameters of one SGR sequence. Background colors and unknown codes are ignored
+fn apply_sgr(style: &mut BaseComponent<'static>, params: &str) {
+    // An empty parameter means 0, anything else that is not a valid code is skipped
+    let mut codes = params.split(';').map(|code| match code.is_empty() {
+        true => Some(0),
+        false => code.parse::<u8>().ok(),
+    });
+    while let Some(code) = codes.next() {
+        match code.unwrap_or(u8::MAX) {
+            0 => *style = BaseComponent::empty(),
+            1 => style.bold = Some(true),
+            3 => style.italic = Some(true),
+            4 => style.underlined = Some(true),
+            9 => style.strikethrough = Some(true),
+            22 => style.bold = None,
+            23 => style.italic = None,
+            24 => style.underlined = None,
+            29 => style.strikethrough = None,
+            code @ (30..=37 | 90..=97) => style.color = color_from_code(code).map(Into::into),
+            39 => style.color = None,
+            code @ (38 | 48) => {
+                let color = match codes.next().flatten() {
+                    Some(5) => codes.next().flatten().map(palette_color),
+                    Some(2) => match (codes.next().flatten(), codes.next().flatten(), codes.next().flatten()) {
+                        (Some(r), Some(g), Some(b)) => Some(HexColor::new_rgb(r, g, b).into()),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                if let (38, Some(color)) = (code, color) {
+                    style.color = Some(color);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Parses text with SGR escape sequences, like the output of [`to_ansi`], into components.
+/// Colors, bold, italic, underline and strikethrough are read. 24-bit colors become hex colors,
+/// 256 palette colors become default colors for the 16 standard entries and hex colors otherwise.
+/// Other escape sequences, including OSC sequences like window titles, are skipped. Text with a
+/// single style becomes one text component, otherwise every styled run is a child of an unstyled
+/// root
+pub fn from_ansi(ansi: &str) -> Component<'static> {
+    let mut runs: Vec<Component<'static>> = Vec::new();
+    let mut style = BaseComponent::empty();
+    let mut text = String::new();
+    let mut chars = ansi.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('[') => {}
+            // OSC and the other string sequences, like a window title, run up to BEL or ST
+            Some(']' | 'P' | 'X' | '^' | '_') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                }
+                continue;
+            }
+            // Intermediate bytes, as in `ESC ( B`, come before the final character
+            Some(' '..='/') => {
+                while chars.next_if(|c| (' '..='/').contains(c)).is_some() {}
+                chars.next();
+                continue;
+            }
+            _ => continue,
+        }
+        let mut params = String::new();
+        let mut end = None;
+        for c in chars.by_ref() {
+            if ('\x40'..='\x7e').contains(&c) {
+                end = Some(c);
+                break;
+            }
+            params.push(c);
+        }
+        if end != Some('m') {
+            continue;
+        }
+        let mut next = style.clone();
+        apply_sgr(&mut next, &params);
+        if next != style && !text.is_empty() {
+            runs.push(TextComponent { text: std::mem::take(&mut text).into(), base: style }.into());
+        }
+        style = next;
+    }
+    if !text.is_empty() {
+        runs.push(TextComponent { text: text.into(), base: style }.into());
+    }
+    match runs.len() {
+        0 => Component::empty(),
+        1 => runs.pop().expect("one run"),
+        _ => {
+            let mut root = Component::empty();
+            root.base_mut().add_extras(runs);
+            root
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::TextComponent;
+    use crate::formatting::HexColor;
+    use super::*;
+
+    #[test]
+    fn inherited_styles() {
+        let mut hex = TextComponent::new("hex");
+        hex.base.color = Some(HexColor::new_rgb(1, 2, 3).into());
+        let mut root = TextComponent::new("red ");
+        root.base.color = Some(DefaultColor::Red.into());
+        root.base.bold = Some(true);
+        let root = root + "child" + hex + TextComponent { base: BaseComponent { bold: Some(false), ..BaseComponent::empty() }, ..TextComponent::new(" thin") };
+        assert_eq!(
+            to_ansi(&root),
+            "\x1b[1;91mred \x1b[0m\x1b[1;91mchild\x1b[0m\x1b[1;38;2;1;2;3mhex\x1b[0m\x1b[91m thin\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn plain() {
+        assert_eq!(to_ansi(&Component::from("plain")), "plain");
+    }
+
+    #[test]
+    fn parse() {
+        let mut red = TextComponent::new("red");
+        red.base.color = Some(DefaultColor::DarkRed.into());
+        assert_eq!(from_ansi("\x1b[31mred\x1b[0m"), red.into());
+        assert_eq!(from_ansi("\x1b[2Kplain\x1b[0m"), Component::from("plain"));
+
+        let parsed = from_ansi("a\x1b[1;38;2;1;2;3mb\x1b[22;38;5;9mc\x1b[38;5;196md\x1b[0m\x1b[4m\x1b[0m");
+        let children = &parsed.base().extra;
+        assert_eq!(parsed.plain_text(), "abcd");
+        assert_eq!(children.len(), 4);
+        assert_eq!(children[0], Component::from("a"));
+        assert_eq!(children[1].base().bold, Some(true));
+        assert_eq!(children[1].base().color, Some(HexColor::new_rgb(1, 2, 3).into()));
+        assert_eq!(children[2].base().bold, None);
+        assert_eq!(children[2].base().color, Some(DefaultColor::Red.into()));
+        assert_eq!(children[3].base().color, Some(HexColor::new_rgb(255, 0, 0).into()));
+    }
+
+    #[test]
+    fn invalid_params() {
+        let parsed = from_ansi("\x1b[1;31ma\x1b[38;2;300;0;0mb\x1b[999;3mc");
+        let children = &parsed.base().extra;
+        assert_eq!(parsed.plain_text(), "abc");
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].plain_text(), "ab");
+        assert_eq!((children[1].base().bold, children[1].base().italic), (Some(true), Some(true)));
+        assert_eq!(children[1].base().color, Some(DefaultColor::DarkRed.into()));
+        assert_eq!(from_ansi("\x1b[1ma\x1b[mb").base().extra[1], Component::from("b"));
+    }
+
+    #[test]
+    fn string_sequences() {
+        assert_eq!(from_ansi("\x1b]0;title\x07a\x1b]8;;https://example.com\x1b\\b\x1b(Bc"), Component::from("abc"));
+    }
+
+    #[test]
+    fn ansi_round_trip() {
+        let mut root = TextComponent::new("red ");
+        root.base.color = Some(DefaultColor::Red.into());
+        root.base.italic = Some(true);
+        let root = root + "child";
+        assert_eq!(from_ansi(&to_ansi(&root)).plain_text(), "red child");
+        assert_eq!(to_ansi(&from_ansi(&to_ansi(&root))), to_ansi(&root));
+    }
+}
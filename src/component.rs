@@ -1,30 +1,283 @@
 use std::borrow::Cow;
-use crate::formatting::{Color};
-use crate::identifier::Identifier;
+use std::collections::HashMap;
+use std::ops::Add;
+use crate::formatting::{gradient_color, Color, ColorParseError, DefaultColor, HexColor, ShadowColor};
+use crate::identifier::{Identifier, IdentifierError, IdentifierInner};
 use serde::{Serialize, Deserialize};
 use uuid::Uuid;
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+/// Written with the fields under `value`. The 1.21.5 form with inline `url`, `command` and
+/// `page` fields is accepted when deserializing, see [`Component::serialize_for`] to write it
+#[derive(Serialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case", tag = "action", content = "value")]
 pub enum ClickEvent<'a> {
     OpenUrl(Cow<'a, str>),
     RunCommand(Cow<'a, str>),
     SuggestCommand(Cow<'a, str>),
-    ChangePage(usize),
+    /// Accepts both a number and a numeric string like vanilla does, but is always serialized as a number
+    ChangePage(i32),
     CopyToClipboard(Cow<'a, str>),
+    /// Added in 1.21.6. Serialized as `{"action":"show_dialog","dialog":...}`
+    #[serde(untagged, serialize_with = "serialize_show_dialog")]
+    ShowDialog(Identifier<'a>),
+    /// Added in 1.21.6. Serialized as `{"action":"custom","id":...,"payload":...}`
+    #[serde(untagged, serialize_with = "serialize_custom")]
+    Custom {
+        id: Identifier<'a>,
+        payload: Option<Cow<'a, str>>,
+    },
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
-#[serde(rename_all = "snake_case", tag = "action", content = "value")]
+fn deserialize_page<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<i32, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Page<'a> {
+        Number(i32),
+        String(Cow<'a, str>),
+    }
+
+    match Page::deserialize(deserializer)? {
+        Page::Number(page) => Ok(page),
+        Page::String(page) => page.trim().parse().map_err(serde::de::Error::custom),
+    }
+}
+
+/// Click actions introduced in 1.21.6, which carry their fields inline instead of in `value`
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case", tag = "action")]
+enum InlineClickEventRef<'r, 'a> {
+    ShowDialog {
+        dialog: &'r Identifier<'a>,
+    },
+    Custom {
+        id: &'r Identifier<'a>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payload: &'r Option<Cow<'a, str>>,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case", tag = "action")]
+enum ClickEventRepr<'a> {
+    OpenUrl {
+        #[serde(alias = "url")]
+        value: Cow<'a, str>,
+    },
+    RunCommand {
+        #[serde(alias = "command")]
+        value: Cow<'a, str>,
+    },
+    SuggestCommand {
+        #[serde(alias = "command")]
+        value: Cow<'a, str>,
+    },
+    ChangePage {
+        #[serde(alias = "page", deserialize_with = "deserialize_page")]
+        value: i32,
+    },
+    CopyToClipboard {
+        value: Cow<'a, str>,
+    },
+    ShowDialog {
+        dialog: Identifier<'a>,
+    },
+    Custom {
+        id: Identifier<'a>,
+        #[serde(default)]
+        payload: Option<Cow<'a, str>>,
+    },
+}
+
+impl<'de, 'a> Deserialize<'de> for ClickEvent<'a> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match ClickEventRepr::deserialize(deserializer)? {
+            ClickEventRepr::OpenUrl { value } => Self::OpenUrl(value),
+            ClickEventRepr::RunCommand { value } => Self::RunCommand(value),
+            ClickEventRepr::SuggestCommand { value } => Self::SuggestCommand(value),
+            ClickEventRepr::ChangePage { value } => Self::ChangePage(value),
+            ClickEventRepr::CopyToClipboard { value } => Self::CopyToClipboard(value),
+            ClickEventRepr::ShowDialog { dialog } => Self::ShowDialog(dialog),
+            ClickEventRepr::Custom { id, payload } => Self::Custom { id, payload },
+        })
+    }
+}
+
+fn serialize_show_dialog<S: serde::Serializer>(dialog: &Identifier, serializer: S) -> Result<S::Ok, S::Error> {
+    InlineClickEventRef::ShowDialog { dialog }.serialize(serializer)
+}
+
+fn serialize_custom<S: serde::Serializer>(id: &Identifier, payload: &Option<Cow<str>>, serializer: S) -> Result<S::Ok, S::Error> {
+    InlineClickEventRef::Custom { id, payload }.serialize(serializer)
+}
+
+/// Text is written under `value`, structured item and entity contents under `contents` and
+/// their legacy SNBT strings under `value`, like vanilla does from 1.16 on. Both fields and the
+/// inline 1.21.5 form are accepted when deserializing
+#[derive(Clone, Debug, PartialEq)]
 pub enum HoverEvent<'a> {
-    ShowText(either::Either<Box<TextComponent<'a>>, Cow<'a, str>>),
-    ShowItem(Cow<'a, str>),
-    ShowEntity(Cow<'a, str>),
+    /// The legacy plain string form is deserialized as a text component
+    ShowText(Box<Component<'a>>),
+    ShowItem(either::Either<ShowItemContents<'a>, Cow<'a, str>>),
+    ShowEntity(either::Either<ShowEntityContents<'a>, Cow<'a, str>>),
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize)]
+#[serde(tag = "action")]
+enum HoverEventRef<'r, 'a> {
+    #[serde(rename = "show_text")]
+    Text {
+        value: &'r Component<'a>,
+    },
+    #[serde(rename = "show_item")]
+    Item {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        contents: Option<&'r ShowItemContents<'a>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        value: Option<&'r Cow<'a, str>>,
+    },
+    #[serde(rename = "show_entity")]
+    Entity {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        contents: Option<&'r ShowEntityContents<'a>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        value: Option<&'r Cow<'a, str>>,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "action")]
+enum HoverEventRepr<'a> {
+    #[serde(rename = "show_text")]
+    Text {
+        #[serde(alias = "contents")]
+        value: Box<Component<'a>>,
+    },
+    /// Without `value` or `contents` the item is inline, as written from 1.21.5 on
+    #[serde(rename = "show_item")]
+    Item {
+        #[serde(default, alias = "contents")]
+        value: Option<HoverContents<'a, ShowItemContents<'a>>>,
+        #[serde(default)]
+        id: Option<Identifier<'a>>,
+        #[serde(default)]
+        count: Option<i32>,
+    },
+    /// Without `value` or `contents` the entity is inline, with its type under `id`
+    #[serde(rename = "show_entity")]
+    Entity {
+        #[serde(default, alias = "contents")]
+        value: Option<HoverContents<'a, ShowEntityContents<'a>>>,
+        #[serde(default)]
+        id: Option<Identifier<'a>>,
+        #[serde(default)]
+        uuid: Option<Uuid>,
+        #[serde(default)]
+        name: Option<Box<Component<'a>>>,
+    },
+}
+
+#[derive(Deserialize)]
 #[serde(untagged)]
+enum HoverContents<'a, T> {
+    Structured(T),
+    Legacy(Cow<'a, str>),
+}
+
+impl<'a, T> From<HoverContents<'a, T>> for either::Either<T, Cow<'a, str>> {
+    fn from(contents: HoverContents<'a, T>) -> Self {
+        match contents {
+            HoverContents::Structured(contents) => Self::Left(contents),
+            HoverContents::Legacy(snbt) => Self::Right(snbt),
+        }
+    }
+}
+
+impl Serialize for HoverEvent<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::ShowText(text) => HoverEventRef::Text { value: text },
+            Self::ShowItem(item) => HoverEventRef::Item { contents: item.as_ref().left(), value: item.as_ref().right() },
+            Self::ShowEntity(entity) =>
+                HoverEventRef::Entity { contents: entity.as_ref().left(), value: entity.as_ref().right() },
+        }.serialize(serializer)
+    }
+}
+
+impl<'de, 'a> Deserialize<'de> for HoverEvent<'a> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match HoverEventRepr::deserialize(deserializer)? {
+            HoverEventRepr::Text { value } => Self::ShowText(value),
+            HoverEventRepr::Item { value: Some(value), .. } => Self::ShowItem(value.into()),
+            HoverEventRepr::Item { value: None, id: Some(id), count } =>
+                Self::ShowItem(either::Either::Left(ShowItemContents { id, count, tag: None })),
+            HoverEventRepr::Entity { value: Some(value), .. } => Self::ShowEntity(value.into()),
+            HoverEventRepr::Entity { value: None, id: Some(entity_type), uuid: Some(id), name } =>
+                Self::ShowEntity(either::Either::Left(ShowEntityContents { entity_type, id, name })),
+            HoverEventRepr::Item { .. } | HoverEventRepr::Entity { .. } =>
+                return Err(serde::de::Error::custom("hover event has no contents")),
+        })
+    }
+}
+
+/// Structured item of [`HoverEvent::ShowItem`]. The legacy SNBT string form is still accepted
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ShowItemContents<'a> {
+    pub id: Identifier<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<Cow<'a, str>>,
+}
+
+/// Structured entity of [`HoverEvent::ShowEntity`]. The legacy SNBT string form is still accepted
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ShowEntityContents<'a> {
+    #[serde(rename = "type")]
+    pub entity_type: Identifier<'a>,
+    pub id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<Box<Component<'a>>>,
+}
+
+/// Cloning keeps every `Cow::Borrowed` field borrowed, so cloning a component built from
+/// borrowed data does not allocate except for the `Box`es of hover texts and separators
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged, try_from = "ComponentRepr<'a>")]
 pub enum Component<'a> {
+    Text(TextComponent<'a>),
+    Translatable(TranslatableComponent<'a>),
+    KeyBind(KeyBindComponent<'a>),
+    Score(ScoreComponent<'a>),
+    Selector(SelectorComponent<'a>),
+    /// Written with an empty `text`, since clients reject components without a content field.
+    /// It therefore parses back as a [`Component::Text`]
+    #[serde(serialize_with = "serialize_base")]
+    Base(BaseComponent<'a>),
+}
+
+fn serialize_base<S: serde::Serializer>(base: &BaseComponent, serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct BaseRef<'r, 'a> {
+        text: &'static str,
+        #[serde(flatten)]
+        base: &'r BaseComponent<'a>,
+    }
+
+    BaseRef { text: "", base }.serialize(serializer)
+}
+
+/// Every form vanilla accepts a component in. The variants are tried in order, so an object is
+/// the first kind whose content field (`text`, `translate`, `keybind`, `score`, `selector`) it has
+/// and only an object with none of them is a [`Component::Base`]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ComponentRepr<'a> {
+    /// A bare string is a text component without any styling
+    Plain(Cow<'a, str>),
+    /// The first element is the parent, the rest are appended to its `extra`
+    List(Vec<Component<'a>>),
+    /// The explicit `type` field added in 1.20.3 takes precedence over the fields present
+    Typed(TypedComponent<'a>),
     Text(TextComponent<'a>),
     Translatable(TranslatableComponent<'a>),
     KeyBind(KeyBindComponent<'a>),
@@ -33,6 +286,47 @@ pub enum Component<'a> {
     Base(BaseComponent<'a>),
 }
 
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum TypedComponent<'a> {
+    Text(TextComponent<'a>),
+    Translatable(TranslatableComponent<'a>),
+    #[serde(rename = "keybind")]
+    KeyBind(KeyBindComponent<'a>),
+    Score(ScoreComponent<'a>),
+    Selector(SelectorComponent<'a>),
+}
+
+impl<'a> TryFrom<ComponentRepr<'a>> for Component<'a> {
+    type Error = &'static str;
+
+    fn try_from(repr: ComponentRepr<'a>) -> Result<Self, Self::Error> {
+        Ok(match repr {
+            ComponentRepr::Plain(text) => Self::Text(TextComponent { text, base: BaseComponent::empty() }),
+            ComponentRepr::List(list) => {
+                let mut list = list.into_iter();
+                let mut parent = list.next().ok_or("Component list is empty")?;
+                let extra: Vec<_> = list.collect();
+                if !extra.is_empty() {
+                    parent.base_mut().add_extras(extra);
+                }
+                parent
+            }
+            ComponentRepr::Typed(TypedComponent::Text(component)) => Self::Text(component),
+            ComponentRepr::Typed(TypedComponent::Translatable(component)) => Self::Translatable(component),
+            ComponentRepr::Typed(TypedComponent::KeyBind(component)) => Self::KeyBind(component),
+            ComponentRepr::Typed(TypedComponent::Score(component)) => Self::Score(component),
+            ComponentRepr::Typed(TypedComponent::Selector(component)) => Self::Selector(component),
+            ComponentRepr::Text(component) => Self::Text(component),
+            ComponentRepr::Translatable(component) => Self::Translatable(component),
+            ComponentRepr::KeyBind(component) => Self::KeyBind(component),
+            ComponentRepr::Score(component) => Self::Score(component),
+            ComponentRepr::Selector(component) => Self::Selector(component),
+            ComponentRepr::Base(component) => Self::Base(component),
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct BaseComponent<'a> {
@@ -50,13 +344,16 @@ pub struct BaseComponent<'a> {
     pub font: Option<Identifier<'a>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<Color<'a>>,
+    #[serde(rename = "shadow_color", skip_serializing_if = "Option::is_none")]
+    pub shadow_color: Option<ShadowColor>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub insertion: Option<Cow<'a, str>>,
-    #[serde(skip_serializing_if = "is_cow_empty")]
+    #[serde(default, skip_serializing_if = "is_cow_empty")]
     pub extra: Cow<'a, [Component<'a>]>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Read from `clickEvent` and the 1.21.5 `click_event`, written as `clickEvent`
+    #[serde(alias = "click_event", skip_serializing_if = "Option::is_none")]
     pub click_event: Option<ClickEvent<'a>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(alias = "hover_event", skip_serializing_if = "Option::is_none")]
     pub hover_event: Option<HoverEvent<'a>>,
 }
 
@@ -72,7 +369,8 @@ pub struct TextComponent<'a> {
 #[serde(rename_all = "camelCase")]
 pub struct TranslatableComponent<'a> {
     pub translate: Cow<'a, str>,
-    #[serde(skip_serializing_if = "is_cow_empty")]
+    /// Vanilla also accepts bare numbers and booleans as arguments, they are read as text components
+    #[serde(default, skip_serializing_if = "is_cow_empty", deserialize_with = "deserialize_args")]
     pub with: Cow<'a, [Component<'a>]>,
     #[serde(flatten)]
     pub base: BaseComponent<'a>,
@@ -100,7 +398,9 @@ pub struct ScoreComponent<'a> {
 pub struct Score<'a> {
     pub name: either::Either<Cow<'a, str>, Uuid>,
     pub objective: Cow<'a, str>,
-    #[serde(skip_serializing_if = "serde_json::Value::is_null")]
+    /// Value to display instead of looking the score up. Removed from the protocol in 1.13 and
+    /// only kept for older data, it is serialized only when it is not `null`
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
     pub value: serde_json::Value,
 }
 
@@ -108,61 +408,270 @@ pub struct Score<'a> {
 #[serde(rename_all = "camelCase")]
 pub struct SelectorComponent<'a> {
     pub selector: Cow<'a, str>,
+    /// Put between the names when the selector matches several entities. Vanilla uses a gray
+    /// `", "` by default. Score components have no separator since they always resolve to one value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub separator: Option<Box<Component<'a>>>,
     #[serde(flatten)]
     pub base: BaseComponent<'a>,
 }
 
-fn is_cow_empty<T: Clone>(value: &Cow<[T]>) -> bool {
-    match value {
-        Cow::Borrowed(ref data) => data.is_empty(),
-        Cow::Owned(ref vec) => vec.is_empty(),
-    }
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ArgRepr<'a> {
+    Number(serde_json::Number),
+    Bool(bool),
+    Component(Box<Component<'a>>),
+}
+
+fn deserialize_args<'de, 'a, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Cow<'a, [Component<'a>]>, D::Error> {
+    let args = Vec::<ArgRepr>::deserialize(deserializer)?
+        .into_iter()
+        .map(|arg| match arg {
+            ArgRepr::Number(number) => TextComponent::new(number.to_string()).into(),
+            ArgRepr::Bool(bool) => TextComponent::new(bool.to_string()).into(),
+            ArgRepr::Component(component) => *component,
+        })
+        .collect();
+    Ok(Cow::Owned(args))
+}
+
+fn is_cow_empty<T: Clone>(value: &[T]) -> bool {
+    value.is_empty()
 }
 
-fn make_owned<T: ToOwned + ?Sized>(cow: &mut Cow<T>) {
+/// Makes the list owned with room for `additional` more elements, so a borrowed list is
+/// copied only once instead of being copied and then grown
+fn reserve_owned<'c, T: Clone>(cow: &'c mut Cow<[T]>, additional: usize) -> &'c mut Vec<T> {
     if let Cow::Borrowed(borrowed) = cow {
-        *cow = Cow::Owned(borrowed.to_owned())
+        let mut owned = Vec::with_capacity(borrowed.len() + additional);
+        owned.extend_from_slice(borrowed);
+        *cow = Cow::Owned(owned);
+    }
+    match cow {
+        Cow::Owned(owned) => {
+            owned.reserve(additional);
+            owned
+        }
+        // Safety. Borrowed was replaced above
+        Cow::Borrowed(_) => unsafe { std::hint::unreachable_unchecked() }
     }
 }
 
-fn add<T: ToOwned + Clone>(into: &mut Cow<[T]>, to_add: T) {
+fn add<T: Clone>(into: &mut Cow<[T]>, to_add: T) {
     match into.is_empty() {
         true => *into = Cow::Owned(vec![to_add]),
-        false => {
-            make_owned(into);
-            match into {
-                Cow::Owned(ref mut owned) => owned.push(to_add),
-                // Safety. guarantied by make_owned
-                _ => unsafe { std::hint::unreachable_unchecked() }
-            }
-        }
+        false => reserve_owned(into, 1).push(to_add),
     }
 }
 
-fn add_values<'a, T: ToOwned + Clone>(into: &mut Cow<'a, [T]>, to_add: Cow<'a, [T]>) {
+fn add_values<'a, T: Clone>(into: &mut Cow<'a, [T]>, to_add: Cow<'a, [T]>) {
     match into.is_empty() {
         true => *into = to_add,
         false => {
-            make_owned(into);
-            match into {
-                Cow::Owned(ref mut owned) => {
-                    let mut to_add = to_add.into();
-                    make_owned(&mut to_add);
-                    match to_add {
-                        Cow::Owned(push) => for to_add in push {
-                            owned.push(to_add)
-                        },
-                        // Safety. guarantied by make_owned
-                        _ => unsafe { std::hint::unreachable_unchecked() }
-                    }
-                }
-                // Safety. guarantied by make_owned
-                _ => unsafe { std::hint::unreachable_unchecked() }
+            let owned = reserve_owned(into, to_add.len());
+            match to_add {
+                Cow::Borrowed(to_add) => owned.extend_from_slice(to_add),
+                Cow::Owned(mut to_add) => owned.append(&mut to_add),
+            }
+        }
+    }
+}
+
+/// Shared empty text component, serialized as `{"text":""}`
+pub static EMPTY_COMPONENT: Component<'static> = Component::EMPTY;
+
+// Safety. All of the font identifiers contain exactly one ':'
+/// Font used when `font` is not set
+pub const DEFAULT_FONT: Identifier<'static> = unsafe { Identifier::from_inner_unchecked(IdentifierInner::Fulled(Cow::Borrowed("minecraft:default"))) };
+/// Unicode font used when the client forces it
+pub const UNIFORM_FONT: Identifier<'static> = unsafe { Identifier::from_inner_unchecked(IdentifierInner::Fulled(Cow::Borrowed("minecraft:uniform"))) };
+/// Enchanting table glyphs
+pub const ALT_FONT: Identifier<'static> = unsafe { Identifier::from_inner_unchecked(IdentifierInner::Fulled(Cow::Borrowed("minecraft:alt"))) };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("Component is nested deeper than {max} levels")]
+pub struct DepthError {
+    pub max: usize,
+}
+
+/// Why [`Component::from_json`] failed. Invalid colors, identifiers and event actions are
+/// reported on their own, every other problem with the json is [`ComponentError::Json`]
+#[derive(Debug, thiserror::Error)]
+pub enum ComponentError {
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Identifier(#[from] IdentifierError),
+    #[error(transparent)]
+    Color(#[from] ColorParseError),
+    #[error("Unknown event action {0:?}")]
+    UnknownAction(String),
+}
+
+const CLICK_ACTIONS: [&str; 7] = [
+    "open_url", "run_command", "suggest_command", "change_page", "copy_to_clipboard", "show_dialog", "custom",
+];
+const HOVER_ACTIONS: [&str; 3] = ["show_text", "show_item", "show_entity"];
+
+/// Looks for the invalid color, identifier or action which made deserializing `json` fail
+fn diagnose(json: &serde_json::Value) -> Option<ComponentError> {
+    let mut stack = vec![json];
+    while let Some(value) = stack.pop() {
+        let object = match value {
+            serde_json::Value::Object(object) => object,
+            serde_json::Value::Array(array) => {
+                stack.extend(array);
+                continue;
+            }
+            _ => continue,
+        };
+        if let Some(serde_json::Value::String(color)) = object.get("color") {
+            if let Err(error) = Color::parse(color) {
+                return Some(error.into());
+            }
+        }
+        if let Some(serde_json::Value::String(font)) = object.get("font") {
+            if let Err(error) = Identifier::new_fulled(font.as_str()) {
+                return Some(error.into());
+            }
+        }
+        for (key, actions) in [
+            ("clickEvent", &CLICK_ACTIONS[..]),
+            ("click_event", &CLICK_ACTIONS[..]),
+            ("hoverEvent", &HOVER_ACTIONS[..]),
+            ("hover_event", &HOVER_ACTIONS[..]),
+        ] {
+            let action = object.get(key).and_then(|event| event.get("action")).and_then(serde_json::Value::as_str);
+            if let Some(action) = action.filter(|action| !actions.contains(action)) {
+                return Some(ComponentError::UnknownAction(action.to_string()));
             }
         }
+        stack.extend(object.values());
+    }
+    None
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BoundedJsonError {
+    #[error(transparent)]
+    Depth(#[from] DepthError),
+    #[error("Json has more than {0} values")]
+    Nodes(usize),
+    #[error("Json contains a string longer than {0} bytes")]
+    StringLength(usize),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// Never holds [`ComponentError::Json`], which is converted into [`BoundedJsonError::Json`]
+    #[error(transparent)]
+    Component(ComponentError),
+}
+
+impl From<ComponentError> for BoundedJsonError {
+    fn from(error: ComponentError) -> Self {
+        match error {
+            ComponentError::Json(error) => Self::Json(error),
+            error => Self::Component(error),
+        }
+    }
+}
+
+/// Builder-style setters shared by every component type
+///
+/// ```
+/// use bird_chat::component::{ClickEvent, Styled, TextComponent};
+/// use bird_chat::formatting::DefaultColor;
+///
+/// let link = TextComponent::from("docs")
+///     .with_color(DefaultColor::Blue)
+///     .on_click(ClickEvent::OpenUrl("https://example.com".into()))
+///     .on_hover_text("Open the docs");
+/// assert_eq!(link.base.color, Some(DefaultColor::Blue.into()));
+/// assert_eq!(link.base.click_event, Some(ClickEvent::OpenUrl("https://example.com".into())));
+/// assert!(link.base.hover_event.is_some());
+/// ```
+pub trait Styled<'a>: Sized {
+    fn style_mut(&mut self) -> &mut BaseComponent<'a>;
+
+    fn on_click(mut self, click_event: ClickEvent<'a>) -> Self {
+        self.style_mut().click_event = Some(click_event);
+        self
+    }
+
+    fn on_click_url(self, url: impl Into<Cow<'a, str>>) -> Self {
+        self.on_click(ClickEvent::OpenUrl(url.into()))
+    }
+
+    fn on_click_command(self, command: impl Into<Cow<'a, str>>) -> Self {
+        self.on_click(ClickEvent::RunCommand(command.into()))
+    }
+
+    fn on_click_suggest(self, command: impl Into<Cow<'a, str>>) -> Self {
+        self.on_click(ClickEvent::SuggestCommand(command.into()))
+    }
+
+    fn on_copy(self, text: impl Into<Cow<'a, str>>) -> Self {
+        self.on_click(ClickEvent::CopyToClipboard(text.into()))
+    }
+
+    fn with_color(mut self, color: impl Into<Color<'a>>) -> Self {
+        self.style_mut().color = Some(color.into());
+        self
+    }
+
+    fn with_font(mut self, font: Identifier<'a>) -> Self {
+        self.style_mut().font = Some(font);
+        self
+    }
+
+    /// Chaining form of [`BaseComponent::add_extra`]
+    fn with_extra(mut self, child: impl Into<Component<'a>>) -> Self {
+        self.style_mut().add_extra(child);
+        self
+    }
+
+    /// See [`BaseComponent::set_insertion`]
+    fn with_insertion(mut self, insertion: impl Into<Cow<'a, str>>) -> Self {
+        self.style_mut().set_insertion(insertion);
+        self
+    }
+
+    fn on_hover(mut self, hover_event: HoverEvent<'a>) -> Self {
+        self.style_mut().hover_event = Some(hover_event);
+        self
+    }
+
+    fn on_hover_text(self, text: impl Into<Component<'a>>) -> Self {
+        self.on_hover(HoverEvent::ShowText(Box::new(text.into())))
+    }
+}
+
+impl<'a> Styled<'a> for BaseComponent<'a> {
+    fn style_mut(&mut self) -> &mut BaseComponent<'a> {
+        self
+    }
+}
+
+impl<'a> Styled<'a> for Component<'a> {
+    fn style_mut(&mut self) -> &mut BaseComponent<'a> {
+        self.base_mut()
     }
 }
 
+macro_rules! impl_styled {
+    ($($component:ident),*) => {
+        $(
+            impl<'a> Styled<'a> for $component<'a> {
+                fn style_mut(&mut self) -> &mut BaseComponent<'a> {
+                    &mut self.base
+                }
+            }
+        )*
+    };
+}
+
+impl_styled!(TextComponent, TranslatableComponent, KeyBindComponent, ScoreComponent, SelectorComponent);
+
 impl<'a> BaseComponent<'a> {
     pub const fn empty() -> Self {
         Self {
@@ -173,6 +682,7 @@ impl<'a> BaseComponent<'a> {
             obfuscated: None,
             font: None,
             color: None,
+            shadow_color: None,
             insertion: None,
             extra: Cow::Borrowed(&[]),
             click_event: None,
@@ -180,6 +690,48 @@ impl<'a> BaseComponent<'a> {
         }
     }
 
+    /// Applies `other` on top of this component: every field `other` sets replaces the one of
+    /// this component, unset fields keep their value. The `extra` of `other` is appended
+    pub fn overlay(&mut self, other: &BaseComponent<'a>) {
+        fn set<T: Clone>(field: &mut Option<T>, other: &Option<T>) {
+            if other.is_some() {
+                field.clone_from(other);
+            }
+        }
+        set(&mut self.bold, &other.bold);
+        set(&mut self.italic, &other.italic);
+        set(&mut self.underlined, &other.underlined);
+        set(&mut self.strikethrough, &other.strikethrough);
+        set(&mut self.obfuscated, &other.obfuscated);
+        set(&mut self.font, &other.font);
+        set(&mut self.color, &other.color);
+        set(&mut self.shadow_color, &other.shadow_color);
+        set(&mut self.insertion, &other.insertion);
+        set(&mut self.click_event, &other.click_event);
+        set(&mut self.hover_event, &other.hover_event);
+        if !other.extra.is_empty() {
+            self.add_extras(other.extra.clone());
+        }
+    }
+
+    /// Unsets every style field, the insertion and both events, leaving `extra` as it is.
+    /// Children still inherit from the parents of this component, unlike with `§r`
+    pub fn clear_styles(&mut self) {
+        let extra = std::mem::replace(&mut self.extra, Cow::Borrowed(&[]));
+        *self = Self { extra, ..Self::empty() };
+    }
+
+    /// Unsets everything including `extra`
+    pub fn reset(&mut self) {
+        *self = Self::empty();
+    }
+
+    /// Sets the text inserted into the chat input when the component is shift-clicked.
+    /// Only works for components shown in chat, not in books, signs or titles
+    pub fn set_insertion(&mut self, insertion: impl Into<Cow<'a, str>>) {
+        self.insertion = Some(insertion.into());
+    }
+
     pub fn add_extra(&mut self, extra: impl Into<Component<'a>>) {
         add(&mut self.extra, extra.into())
     }
@@ -187,50 +739,2333 @@ impl<'a> BaseComponent<'a> {
     pub fn add_extras(&mut self, extras: impl Into<Cow<'a, [Component<'a>]>>) {
         add_values(&mut self.extra, extras.into());
     }
+
+    /// Compares every styling field, ignoring `extra` and events
+    pub(crate) fn same_style(&self, other: &Self) -> bool {
+        self.bold == other.bold &&
+            self.italic == other.italic &&
+            self.underlined == other.underlined &&
+            self.strikethrough == other.strikethrough &&
+            self.obfuscated == other.obfuscated &&
+            self.font == other.font &&
+            self.color == other.color &&
+            self.shadow_color == other.shadow_color &&
+            self.insertion == other.insertion
+    }
+
+    /// Style of a root component as vanilla renders it: no color and all decorations disabled
+    pub(crate) fn root_style() -> Self {
+        Self {
+            bold: Some(false),
+            italic: Some(false),
+            underlined: Some(false),
+            strikethrough: Some(false),
+            obfuscated: Some(false),
+            ..Self::empty()
+        }
+    }
+
+    /// Copies the inheritable fields, everything but `extra`
+    pub(crate) fn style(&self) -> Self {
+        Self {
+            bold: self.bold,
+            italic: self.italic,
+            underlined: self.underlined,
+            strikethrough: self.strikethrough,
+            obfuscated: self.obfuscated,
+            font: self.font.clone(),
+            color: self.color.clone(),
+            shadow_color: self.shadow_color,
+            insertion: self.insertion.clone(),
+            extra: Cow::Borrowed(&[]),
+            click_event: self.click_event.clone(),
+            hover_event: self.hover_event.clone(),
+        }
+    }
+
+    /// Fills every unset field but `extra` with the value of `parent`. Like vanilla, this
+    /// includes `insertion` and both events
+    pub(crate) fn inherit(&mut self, parent: &Self) {
+        self.bold = self.bold.or(parent.bold);
+        self.italic = self.italic.or(parent.italic);
+        self.underlined = self.underlined.or(parent.underlined);
+        self.strikethrough = self.strikethrough.or(parent.strikethrough);
+        self.obfuscated = self.obfuscated.or(parent.obfuscated);
+        self.shadow_color = self.shadow_color.or(parent.shadow_color);
+        if self.font.is_none() {
+            self.font = parent.font.clone();
+        }
+        if self.color.is_none() {
+            self.color = parent.color.clone();
+        }
+        if self.insertion.is_none() {
+            self.insertion = parent.insertion.clone();
+        }
+        if self.click_event.is_none() {
+            self.click_event = parent.click_event.clone();
+        }
+        if self.hover_event.is_none() {
+            self.hover_event = parent.hover_event.clone();
+        }
+    }
+
+    const fn has_events(&self) -> bool {
+        self.click_event.is_some() || self.hover_event.is_some()
+    }
 }
 
-impl<'a> TranslatableComponent<'a> {
-    pub fn add_arg(&mut self, arg: impl Into<Component<'a>>) {
-        add(&mut self.with, arg.into())
+/// Colors every character of `text` along the gradient, with the stops spread evenly over the
+/// text. The characters are children of an empty text component. Without stops the text is
+/// left uncolored
+pub fn gradient(text: &str, stops: &[HexColor]) -> Component<'static> {
+    let mut parent = TextComponent::new("");
+    match stops.is_empty() {
+        true => parent.text = Cow::Owned(text.to_string()),
+        false => {
+            let total = text.chars().count();
+            parent.base.extra = text.chars()
+                .enumerate()
+                .map(|(index, c)| TextComponent {
+                    text: Cow::Owned(c.to_string()),
+                    base: BaseComponent { color: Some(gradient_color(stops, index, total).into()), ..BaseComponent::empty() },
+                }.into())
+                .collect();
+        }
     }
+    parent.into()
+}
 
-    pub fn add_args(&mut self, args: impl Into<Cow<'a, [Component<'a>]>>) {
-        add_values(&mut self.with, args.into());
+/// Colors each character of `text` with the next rainbow color out of the default colors, so
+/// it also renders on clients before 1.16. Whitespace is colored too but does not advance the
+/// colors. The characters are children of an empty text component
+pub fn rainbow(text: &str) -> Component<'static> {
+    const COLORS: [DefaultColor; 7] = [
+        DefaultColor::Red,
+        DefaultColor::Gold,
+        DefaultColor::Yellow,
+        DefaultColor::BrightGreen,
+        DefaultColor::Cyan,
+        DefaultColor::Blue,
+        DefaultColor::Pink,
+    ];
+    let mut parent = TextComponent::new("");
+    let mut index = 0;
+    parent.base.extra = text.chars()
+        .map(|c| {
+            let color = COLORS[index % COLORS.len()];
+            if !c.is_whitespace() {
+                index += 1;
+            }
+            TextComponent {
+                text: Cow::Owned(c.to_string()),
+                base: BaseComponent { color: Some(color.into()), ..BaseComponent::empty() },
+            }.into()
+        })
+        .collect();
+    parent.into()
+}
+
+/// Puts `separator` between the components, all of them children of an empty text component
+pub fn join<'a>(components: impl IntoIterator<Item = Component<'a>>, separator: &Component<'a>) -> Component<'a> {
+    let mut parts = Vec::new();
+    for component in components {
+        if !parts.is_empty() {
+            parts.push(separator.clone());
+        }
+        parts.push(component);
     }
+    let mut parent = TextComponent::new("");
+    parent.base.add_extras(parts);
+    parent.into()
 }
 
-impl<'a> From<TextComponent<'a>> for Component<'a> {
-    fn from(component: TextComponent<'a>) -> Self {
-        Self::Text(component)
+/// Splices `args` into the `{}` placeholders of `template`, in order, like `format!` does with
+/// strings. The literal parts and the arguments become the children of an empty text component,
+/// so every argument keeps its own style. `{{` and `}}` are literal braces, placeholders without
+/// an argument are kept as is and unused arguments are dropped
+///
+/// ```
+/// use bird_chat::component::{format_components, Component, Styled, TextComponent};
+/// use bird_chat::formatting::DefaultColor;
+///
+/// let player: Component = TextComponent::new("Steve").with_color(DefaultColor::Yellow).into();
+/// let joined = format_components("{} joined the game", &[player]);
+/// assert_eq!(joined.plain_text(), "Steve joined the game");
+/// ```
+pub fn format_components<'a>(template: &str, args: &[Component<'a>]) -> Component<'a> {
+    let mut parts: Vec<Component<'a>> = Vec::new();
+    let mut literal = String::new();
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        literal.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        if let Some(after) = tail.strip_prefix("{{").or_else(|| tail.strip_prefix("}}")) {
+            literal.push_str(&tail[..1]);
+            rest = after;
+            continue;
+        }
+        let after = match tail.strip_prefix("{}") {
+            Some(after) => after,
+            None => {
+                literal.push_str(&tail[..1]);
+                rest = &tail[1..];
+                continue;
+            }
+        };
+        match args.next() {
+            Some(arg) => {
+                if !literal.is_empty() {
+                    parts.push(TextComponent::new(std::mem::take(&mut literal)).into());
+                }
+                parts.push(arg.clone());
+            }
+            None => literal.push_str("{}"),
+        }
+        rest = after;
+    }
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        parts.push(TextComponent::new(literal).into());
     }
+    let mut parent = TextComponent::new("");
+    parent.base.add_extras(parts);
+    parent.into()
 }
 
-impl<'a> From<TranslatableComponent<'a>> for Component<'a> {
-    fn from(component: TranslatableComponent<'a>) -> Self {
-        Self::Translatable(component)
+fn resolve_translations<'a>(component: &mut Component<'a>, translations: &HashMap<String, String>) {
+    if let Component::Translatable(translatable) = component {
+        *component = translatable.resolve_shallow(translations);
     }
+    component.for_each_child_mut(|child| resolve_translations(child, translations));
 }
 
-impl<'a> From<ScoreComponent<'a>> for Component<'a> {
-    fn from(component: ScoreComponent<'a>) -> Self {
-        Self::Score(component)
+impl<'a> TextComponent<'a> {
+    pub fn new(text: impl Into<Cow<'a, str>>) -> Self {
+        Self { text: text.into(), base: BaseComponent::empty() }
+    }
+
+    /// Whether `next` can be appended to the text of this component without changing the result
+    fn can_merge(&self, next: &Self) -> bool {
+        self.base.extra.is_empty() &&
+            !self.base.has_events() &&
+            !next.base.has_events() &&
+            self.base.same_style(&next.base)
     }
 }
 
-impl<'a> From<SelectorComponent<'a>> for Component<'a> {
-    fn from(component: SelectorComponent<'a>) -> Self {
-        Self::Selector(component)
+/// Builds a [`Component::Base`], a styled container without text of its own, created by
+/// [`BaseComponent::builder`]. It is sent as a text component with empty text
+///
+/// ```
+/// use bird_chat::component::{BaseComponent, Component, Styled};
+/// use bird_chat::formatting::DefaultColor;
+///
+/// let container = BaseComponent::builder()
+///     .with_color(DefaultColor::Red)
+///     .extra("Hello, ")
+///     .extra("world")
+///     .build();
+/// assert!(matches!(container, Component::Base(_)));
+/// assert_eq!(container.plain_text(), "Hello, world");
+/// assert_eq!(container.color_at(&[1]), Some(DefaultColor::Red.into()));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct BaseBuilder<'a> {
+    component: BaseComponent<'a>,
+}
+
+impl<'a> BaseBuilder<'a> {
+    pub fn extra(mut self, child: impl Into<Component<'a>>) -> Self {
+        self.component.add_extra(child);
+        self
+    }
+
+    pub fn build(self) -> Component<'a> {
+        Component::Base(self.component)
     }
 }
 
-impl<'a> From<KeyBindComponent<'a>> for Component<'a> {
-    fn from(component: KeyBindComponent<'a>) -> Self {
-        Self::KeyBind(component)
+impl<'a> Styled<'a> for BaseBuilder<'a> {
+    fn style_mut(&mut self) -> &mut BaseComponent<'a> {
+        &mut self.component
     }
 }
 
-impl<'a> From<BaseComponent<'a>> for Component<'a> {
-    fn from(component: BaseComponent<'a>) -> Self {
-        Self::Base(component)
+impl<'a> BaseComponent<'a> {
+    pub const fn builder() -> BaseBuilder<'a> {
+        BaseBuilder { component: BaseComponent::empty() }
+    }
+}
+
+/// Builds a [`TranslatableComponent`], created by [`TranslatableComponent::builder`]
+///
+/// ```
+/// use bird_chat::component::{Component, Styled, TranslatableComponent};
+///
+/// let message = TranslatableComponent::builder("chat.type.text")
+///     .arg("Steve")
+///     .arg("hello")
+///     .on_click_suggest("/msg Steve ")
+///     .build();
+/// assert_eq!(message.with.len(), 2);
+/// assert_eq!(message.with[0], Component::from("Steve"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TranslatableBuilder<'a> {
+    component: TranslatableComponent<'a>,
+}
+
+impl<'a> TranslatableBuilder<'a> {
+    pub fn arg(mut self, arg: impl Into<Component<'a>>) -> Self {
+        self.component.add_arg(arg);
+        self
+    }
+
+    pub fn args(mut self, args: impl IntoIterator<Item = impl Into<Component<'a>>>) -> Self {
+        let args: Vec<_> = args.into_iter().map(Into::into).collect();
+        self.component.add_args(args);
+        self
+    }
+
+    pub fn build(self) -> TranslatableComponent<'a> {
+        self.component
+    }
+}
+
+impl<'a> Styled<'a> for TranslatableBuilder<'a> {
+    fn style_mut(&mut self) -> &mut BaseComponent<'a> {
+        &mut self.component.base
+    }
+}
+
+impl<'a> TranslatableComponent<'a> {
+    pub fn builder(translate: impl Into<Cow<'a, str>>) -> TranslatableBuilder<'a> {
+        TranslatableBuilder {
+            component: TranslatableComponent {
+                translate: translate.into(),
+                with: Cow::Borrowed(&[]),
+                base: BaseComponent::empty(),
+            },
+        }
+    }
+
+    /// The `with` arguments only, `extra` children are in [`BaseComponent::extra`]
+    pub fn args(&self) -> &[Component<'a>] {
+        &self.with
+    }
+
+    pub fn add_arg(&mut self, arg: impl Into<Component<'a>>) {
+        add(&mut self.with, arg.into())
+    }
+
+    pub fn add_args(&mut self, args: impl Into<Cow<'a, [Component<'a>]>>) {
+        add_values(&mut self.with, args.into());
+    }
+
+    /// Substitutes the arguments into the format string `translations` has for the key, producing
+    /// a text component with the same style whose `extra` holds the literal parts and arguments
+    /// followed by the original `extra`. Both `%s` and `%1$s` placeholders are supported, and
+    /// `%%` is a literal percent sign. Placeholders referring to missing arguments are kept as is.
+    /// If there is no translation for the key, the key itself is used as the text.
+    /// Translatable components anywhere in the arguments and in `extra` are resolved recursively
+    pub fn resolve(&self, translations: &HashMap<String, String>) -> Component<'a> {
+        let mut resolved = self.resolve_shallow(translations);
+        resolved.for_each_child_mut(|child| resolve_translations(child, translations));
+        resolved
     }
-}
\ No newline at end of file
+
+    /// [`TranslatableComponent::resolve`] without resolving the arguments and children
+    fn resolve_shallow(&self, translations: &HashMap<String, String>) -> Component<'a> {
+        let mut base = BaseComponent { extra: Cow::Borrowed(&[]), ..self.base.clone() };
+        let format = match translations.get(self.translate.as_ref()) {
+            Some(format) => format,
+            None => {
+                base.extra = self.base.extra.clone();
+                return TextComponent { text: self.translate.clone(), base }.into();
+            }
+        };
+        let mut parts: Vec<Component<'a>> = Vec::new();
+        let mut literal = String::new();
+        let mut next_index = 0;
+        let mut rest = format.as_str();
+        while let Some(start) = rest.find('%') {
+            literal.push_str(&rest[..start]);
+            rest = &rest[start + 1..];
+            if let Some(after) = rest.strip_prefix('%') {
+                literal.push('%');
+                rest = after;
+                continue;
+            }
+            let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+            let (index, after) = match rest[digits..].strip_prefix("$s") {
+                Some(after) if digits > 0 => (rest[..digits].parse::<usize>().ok().and_then(|index| index.checked_sub(1)), after),
+                _ => match rest.strip_prefix('s') {
+                    Some(after) => {
+                        next_index += 1;
+                        (Some(next_index - 1), after)
+                    }
+                    None => {
+                        literal.push('%');
+                        continue;
+                    }
+                },
+            };
+            match index.and_then(|index| self.with.get(index)) {
+                Some(arg) => {
+                    if !literal.is_empty() {
+                        parts.push(TextComponent::new(std::mem::take(&mut literal)).into());
+                    }
+                    parts.push(arg.clone());
+                }
+                None => literal.push_str(&format[format.len() - rest.len() - 1..format.len() - after.len()]),
+            }
+            rest = after;
+        }
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            parts.push(TextComponent::new(literal).into());
+        }
+        base.add_extras(parts);
+        base.add_extras(self.base.extra.clone());
+        TextComponent { text: Cow::Borrowed(""), base }.into()
+    }
+}
+
+impl<'a> SelectorComponent<'a> {
+    /// Separator put between the matched names, the own one or vanilla's gray `", "`
+    pub fn separator_or_default(&self) -> Component<'a> {
+        match &self.separator {
+            Some(separator) => separator.as_ref().clone(),
+            None => {
+                let mut separator = TextComponent::new(", ");
+                separator.base.color = Some(DefaultColor::Gray.into());
+                separator.into()
+            }
+        }
+    }
+
+    /// Replaces the selector with the names of the entities it matched, like the server does.
+    /// Produces a text component with the same style whose `extra` holds the names joined by
+    /// [`SelectorComponent::separator_or_default`], followed by the original `extra`. Its
+    /// [`Component::plain_text`] is the joined list, while an unresolved selector only shows itself
+    pub fn resolve(&self, names: impl IntoIterator<Item = impl Into<Component<'a>>>) -> Component<'a> {
+        let separator = self.separator_or_default();
+        let mut parts = Vec::new();
+        for name in names {
+            if !parts.is_empty() {
+                parts.push(separator.clone());
+            }
+            parts.push(name.into());
+        }
+        let mut base = BaseComponent { extra: Cow::Borrowed(&[]), ..self.base.clone() };
+        base.add_extras(parts);
+        base.add_extras(self.base.extra.clone());
+        TextComponent { text: Cow::Borrowed(""), base }.into()
+    }
+}
+
+/// Builds a [`ScoreComponent`], created by [`ScoreComponent::builder`]
+///
+/// ```
+/// use bird_chat::component::ScoreComponent;
+///
+/// let kills = ScoreComponent::builder("kills").player("Steve").build();
+/// assert_eq!(kills.score.name, either::Either::Left("Steve".into()));
+/// assert_eq!(kills.score.objective, "kills");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScoreBuilder<'a> {
+    component: ScoreComponent<'a>,
+}
+
+impl<'a> ScoreBuilder<'a> {
+    /// Shows the score of the player or score holder with this name. `*` is the viewer
+    pub fn player(mut self, name: impl Into<Cow<'a, str>>) -> Self {
+        self.component.score.name = either::Either::Left(name.into());
+        self
+    }
+
+    pub fn entity(mut self, uuid: Uuid) -> Self {
+        self.component.score.name = either::Either::Right(uuid);
+        self
+    }
+
+    /// See [`Score::value`]
+    pub fn value(mut self, value: impl Into<serde_json::Value>) -> Self {
+        self.component.score.set_value(value);
+        self
+    }
+
+    pub fn build(self) -> ScoreComponent<'a> {
+        self.component
+    }
+}
+
+impl<'a> Styled<'a> for ScoreBuilder<'a> {
+    fn style_mut(&mut self) -> &mut BaseComponent<'a> {
+        &mut self.component.base
+    }
+}
+
+impl<'a> ScoreComponent<'a> {
+    /// The score holder defaults to `*`, the player viewing the component
+    pub fn builder(objective: impl Into<Cow<'a, str>>) -> ScoreBuilder<'a> {
+        ScoreBuilder {
+            component: ScoreComponent {
+                score: Score {
+                    name: either::Either::Left(Cow::Borrowed("*")),
+                    objective: objective.into(),
+                    value: serde_json::Value::Null,
+                },
+                base: BaseComponent::empty(),
+            },
+        }
+    }
+}
+
+impl Score<'_> {
+    /// Moves a name which is a valid UUID string into the UUID arm, so the score holder is
+    /// resolved as an entity the same way regardless of how the name was sent
+    pub fn normalize(&mut self) {
+        if let either::Either::Left(name) = &self.name {
+            if let Ok(uuid) = Uuid::parse_str(name) {
+                self.name = either::Either::Right(uuid);
+            }
+        }
+    }
+
+    /// The value if it is a string
+    pub fn value_as_str(&self) -> Option<&str> {
+        self.value.as_str()
+    }
+
+    /// The value if it is an integer or a string holding one
+    pub fn value_as_i64(&self) -> Option<i64> {
+        match &self.value {
+            serde_json::Value::String(value) => value.parse().ok(),
+            value => value.as_i64(),
+        }
+    }
+
+    /// Sets the value, `serde_json::Value::Null` removes it
+    pub fn set_value(&mut self, value: impl Into<serde_json::Value>) {
+        self.value = value.into();
+    }
+}
+
+impl<'a> Component<'a> {
+    /// Serializes into compact json, the form vanilla sends
+    ///
+    /// ```
+    /// use bird_chat::component::{Component, Styled, TextComponent};
+    ///
+    /// let component: Component = TextComponent::new("hi").on_click_url("https://example.com").into();
+    /// let json = component.to_json().unwrap();
+    /// assert_eq!(Component::from_json(&json).unwrap(), component);
+    /// ```
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Like [`Component::to_json`], but fails instead of recursing into trees with more than
+    /// `max_depth` levels, counting hover texts and separators as [`Component::depth`] does. Only
+    /// the depth check uses an explicit stack: the json is then written by the recursive serde
+    /// serializer, which takes several stack frames per level. A large `max_depth` can still
+    /// overflow the stack, choose one that fits the stack of the calling thread
+    pub fn to_json_bounded(&self, max_depth: usize) -> Result<String, BoundedJsonError> {
+        self.validate_depth(max_depth)?;
+        Ok(self.to_json()?)
+    }
+
+    /// Serializes a [`Component::compact`]ed copy
+    pub fn to_json_compact(&self) -> Result<String, serde_json::Error> {
+        let mut compact = self.clone();
+        compact.compact();
+        compact.to_json()
+    }
+
+    /// Parses json in any of the forms vanilla accepts. Parsing is only repeated to find the
+    /// cause of the error if it fails, see [`ComponentError`]
+    pub fn from_json(json: &str) -> Result<Self, ComponentError> {
+        serde_json::from_str(json).map_err(|error| {
+            serde_json::from_str(json).ok().as_ref().and_then(diagnose).unwrap_or(ComponentError::Json(error))
+        })
+    }
+
+    /// The json of this component as a json string literal, for packets carrying a component
+    /// inside of a string field
+    pub fn to_network_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self.to_json()?)
+    }
+
+    /// Parses the form [`Component::to_network_string`] produces
+    pub fn from_network_string(string: &str) -> Result<Self, ComponentError> {
+        Self::from_json(&serde_json::from_str::<String>(string)?)
+    }
+
+    /// Streams the json of [`Component::to_json`] into `writer` without building the string first
+    pub fn to_writer<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        Ok(serde_json::to_writer(writer, self)?)
+    }
+
+    /// Like [`Component::from_json`], reading the json from `reader`. Read errors are reported
+    /// as [`ComponentError::Json`]
+    pub fn from_reader(mut reader: impl std::io::Read) -> Result<Self, ComponentError> {
+        let mut json = String::new();
+        reader.read_to_string(&mut json).map_err(serde_json::Error::io)?;
+        Self::from_json(&json)
+    }
+
+    /// Same as [`Component::empty`], usable where a constant is needed. Nothing is allocated.
+    /// For a `&'static` reference use [`EMPTY_COMPONENT`]
+    pub const EMPTY: Self = Self::empty();
+
+    /// Unstyled text component without text
+    pub const fn empty() -> Self {
+        Self::Text(TextComponent { text: Cow::Borrowed(""), base: BaseComponent::empty() })
+    }
+
+    /// Whether neither this component nor any of its descendants display anything, see
+    /// [`Component::content`]. Whitespace counts as content
+    pub fn is_empty(&self) -> bool {
+        self.iter().all(|component| component.content().is_empty())
+    }
+
+    pub const fn base(&self) -> &BaseComponent<'a> {
+        match self {
+            Self::Text(component) => &component.base,
+            Self::Translatable(component) => &component.base,
+            Self::KeyBind(component) => &component.base,
+            Self::Score(component) => &component.base,
+            Self::Selector(component) => &component.base,
+            Self::Base(component) => component,
+        }
+    }
+
+    pub fn base_mut(&mut self) -> &mut BaseComponent<'a> {
+        match self {
+            Self::Text(component) => &mut component.base,
+            Self::Translatable(component) => &mut component.base,
+            Self::KeyBind(component) => &mut component.base,
+            Self::Score(component) => &mut component.base,
+            Self::Selector(component) => &mut component.base,
+            Self::Base(component) => component,
+        }
+    }
+
+    /// Text this component displays itself, without its children. Components resolved by the
+    /// client display their raw value: the translation key, the key bind or the selector.
+    /// A score displays its `value` if that is a string or a number
+    pub fn content(&self) -> Cow<'_, str> {
+        match self {
+            Self::Text(component) => Cow::Borrowed(&component.text),
+            Self::Translatable(component) => Cow::Borrowed(&component.translate),
+            Self::KeyBind(component) => Cow::Borrowed(&component.key_bind),
+            Self::Score(component) => match &component.score.value {
+                serde_json::Value::String(value) => Cow::Borrowed(value),
+                serde_json::Value::Number(value) => Cow::Owned(value.to_string()),
+                _ => Cow::Borrowed(""),
+            },
+            Self::Selector(component) => Cow::Borrowed(&component.selector),
+            Self::Base(_) => Cow::Borrowed(""),
+        }
+    }
+
+    /// Concatenated [`Component::content`] of this component and all of its descendants
+    pub fn plain_text(&self) -> String {
+        self.iter().map(Component::content).collect()
+    }
+
+    /// Like [`Component::plain_text`], with obfuscated text handled as `obfuscated` says.
+    /// Obfuscation is inherited like the other decorations
+    pub fn plain_text_with(&self, obfuscated: ObfuscatedText) -> String {
+        let mut text = String::new();
+        self.walk_styled(&BaseComponent::root_style(), &mut |component, style| {
+            let content = component.content();
+            match obfuscated {
+                ObfuscatedText::Placeholder(placeholder) if style.obfuscated == Some(true) =>
+                    text.extend(content.chars().map(|_| placeholder)),
+                _ => text.push_str(&content),
+            }
+        });
+        text
+    }
+
+    /// Click events of this component and all of its descendants, in
+    /// [`Component::iter_including_events`] order, so events inside of hover texts, entity names
+    /// and selector separators are found too
+    pub fn click_events(&self) -> Vec<&ClickEvent<'a>> {
+        self.iter_including_events().filter_map(|component| component.base().click_event.as_ref()).collect()
+    }
+
+    /// Hover events of this component and all of its descendants, in
+    /// [`Component::iter_including_events`] order like [`Component::click_events`]
+    pub fn hover_events(&self) -> Vec<&HoverEvent<'a>> {
+        self.iter_including_events().filter_map(|component| component.base().hover_event.as_ref()).collect()
+    }
+
+    /// Number of levels of the tree, 1 for a component without children. Hover texts, entity
+    /// names and selector separators count as a level below their component like children do,
+    /// since serializing and visiting recurse into them as well. Computed without recursion so
+    /// it is safe on untrusted trees
+    pub fn depth(&self) -> usize {
+        let mut max = 0;
+        let mut stack = vec![(self, 1)];
+        while let Some((component, depth)) = stack.pop() {
+            max = max.max(depth);
+            stack.extend(component.children().chain(component.payloads()).map(|child| (child, depth + 1)));
+        }
+        max
+    }
+
+    /// Rejects trees with more than `max` levels, see [`Component::depth`]
+    pub fn validate_depth(&self, max: usize) -> Result<(), DepthError> {
+        let mut stack = vec![(self, 1)];
+        while let Some((component, depth)) = stack.pop() {
+            if depth > max {
+                return Err(DepthError { max });
+            }
+            stack.extend(component.children().chain(component.payloads()).map(|child| (child, depth + 1)));
+        }
+        Ok(())
+    }
+
+    /// Compares only the displayed text of both trees, ignoring styles, events and how the text
+    /// is split between components
+    pub fn text_eq(&self, other: &Component) -> bool {
+        self.plain_text() == other.plain_text()
+    }
+
+    /// Orders both trees by their [`Component::plain_text`], for sorting components alphabetically.
+    /// Like [`str::cmp`] this compares code points, not locale aware
+    pub fn text_cmp(&self, other: &Component) -> std::cmp::Ordering {
+        self.plain_text().cmp(&other.plain_text())
+    }
+
+    /// Number of characters of [`Component::plain_text`], without building the string
+    pub fn content_len(&self) -> usize {
+        self.iter().map(|component| component.content().chars().count()).sum()
+    }
+
+    /// Adds an unstyled `"\n"` text child, so following children start on a new line
+    pub fn append_newline(&mut self) {
+        self.base_mut().add_extra(TextComponent::new("\n"));
+    }
+
+    /// Cuts the tree after `max_chars` characters of [`Component::plain_text`] and appends `"…"`.
+    /// The component where the limit is hit becomes a text component with its own style, keeping
+    /// only the characters that fit, and the ellipsis is added as its child so it inherits that
+    /// style. Everything after it is dropped. A tree that already fits is returned unchanged.
+    /// Translatable, key bind, score and selector components are counted by their
+    /// [`Component::content`] and never cut: when the limit falls inside one, it is replaced by
+    /// the styled ellipsis, so no partial translation key becomes visible text
+    pub fn truncate(&self, max_chars: usize) -> Component<'a> {
+        let mut truncated = self.clone();
+        if self.content_len() > max_chars {
+            let mut remaining = max_chars;
+            truncate_inner(&mut truncated, &mut remaining);
+        }
+        truncated
+    }
+
+    /// Translatable arguments followed by `extra` children
+    pub(crate) fn children(&self) -> impl Iterator<Item = &Component<'a>> {
+        let args = match self {
+            Self::Translatable(translatable) => translatable.with.as_ref(),
+            _ => &[],
+        };
+        args.iter().chain(self.base().extra.iter())
+    }
+
+    /// Calls `f` on every component in the order of [`Component::iter`] together with its
+    /// resolved style, see [`Component::resolve_styles`]. The style also carries the `insertion`
+    /// and events of the component or the ones it inherits
+    pub(crate) fn walk_styled(&self, parent: &BaseComponent<'a>, f: &mut impl FnMut(&Component<'a>, &BaseComponent<'a>)) {
+        let mut style = self.base().style();
+        style.inherit(parent);
+        f(self, &style);
+        self.children().for_each(|child| child.walk_styled(&style, f));
+    }
+
+    /// Flattens the tree into the non-empty [`Component::content`] of every component in
+    /// [`Component::iter`] order, each with its resolved style as in [`Component::wrap`]
+    pub fn into_spans(&self) -> Vec<TextSpan<'a>> {
+        let mut spans = Vec::new();
+        self.walk_styled(&BaseComponent::root_style(), &mut |component, style| {
+            let content = component.content();
+            if !content.is_empty() {
+                spans.push(TextSpan { text: content.into_owned(), style: style.clone() });
+            }
+        });
+        spans
+    }
+
+    /// Splits the visible text into lines of at most `max_chars` characters, breaking on
+    /// whitespace and on `\n`. Words longer than a line are split. Each line is an unstyled
+    /// text component whose `extra` holds one text component per styled run, carrying the
+    /// resolved style of the component the run came from
+    pub fn wrap(&self, max_chars: usize) -> Vec<Component<'a>> {
+        let max_chars = max_chars.max(1);
+        let mut styles: Vec<BaseComponent<'a>> = Vec::new();
+        let mut chars: Vec<(char, usize)> = Vec::new();
+        self.walk_styled(&BaseComponent::root_style(), &mut |component, style| {
+            let content = component.content();
+            if !content.is_empty() {
+                chars.extend(content.chars().map(|c| (c, styles.len())));
+                styles.push(style.clone());
+            }
+        });
+
+        let mut lines: Vec<Vec<(char, usize)>> = Vec::new();
+        let mut line: Vec<(char, usize)> = Vec::new();
+        let mut spaces: Vec<(char, usize)> = Vec::new();
+        let mut rest = chars.as_slice();
+        while let Some(&(c, _)) = rest.first() {
+            if c == '\n' {
+                lines.push(std::mem::take(&mut line));
+                spaces.clear();
+                rest = &rest[1..];
+                continue;
+            }
+            let is_space = c.is_whitespace();
+            let len = rest.iter()
+                .position(|(c, _)| *c == '\n' || c.is_whitespace() != is_space)
+                .unwrap_or(rest.len());
+            let (segment, after) = rest.split_at(len);
+            rest = after;
+            if is_space {
+                spaces.extend_from_slice(segment);
+                continue;
+            }
+            let mut word = segment;
+            if !line.is_empty() && line.len() + spaces.len() + word.len() > max_chars {
+                lines.push(std::mem::take(&mut line));
+                spaces.clear();
+            }
+            if line.len() + spaces.len() + word.len() > max_chars {
+                spaces.clear();
+            }
+            line.append(&mut spaces);
+            while line.len() + word.len() > max_chars {
+                let (head, tail) = word.split_at(max_chars - line.len());
+                line.extend_from_slice(head);
+                lines.push(std::mem::take(&mut line));
+                word = tail;
+            }
+            line.extend_from_slice(word);
+        }
+        if !line.is_empty() || lines.is_empty() {
+            lines.push(line);
+        }
+
+        lines.into_iter()
+            .map(|line| {
+                let mut parent = TextComponent::new("");
+                let runs: Vec<Component<'a>> = line.chunk_by(|(_, left), (_, right)| left == right)
+                    .map(|run| TextComponent {
+                        text: Cow::Owned(run.iter().map(|(c, _)| *c).collect()),
+                        base: styles[run[0].1].clone(),
+                    }.into())
+                    .collect();
+                if !runs.is_empty() {
+                    parent.base.add_extras(runs);
+                }
+                parent.into()
+            })
+            .collect()
+    }
+
+    /// Iterates over this component and all of its descendants in depth-first order.
+    /// Translatable arguments (`with`) are visited before `extra` children
+    pub fn iter(&self) -> Iter<'_, 'a> {
+        Iter { stack: vec![self], payloads: false }
+    }
+
+    /// Like [`Component::iter`], but also descends into the components shown by
+    /// [`HoverEvent::ShowText`], the name of [`HoverEvent::ShowEntity`] and selector separators,
+    /// see [`Component::payloads`]. They are visited right after their component, before its
+    /// children. Subtrees shared through borrowed lists are yielded every time they are reached,
+    /// like [`Component::iter`] does, and the walk uses an explicit stack so deep trees are fine
+    pub fn iter_including_events(&self) -> Iter<'_, 'a> {
+        Iter { stack: vec![self], payloads: true }
+    }
+
+    /// Components which are part of this component without being its children: the text of
+    /// [`HoverEvent::ShowText`], the name of [`HoverEvent::ShowEntity`] and the separator of a
+    /// selector
+    pub(crate) fn payloads(&self) -> impl Iterator<Item = &Component<'a>> {
+        let hover = match &self.base().hover_event {
+            Some(HoverEvent::ShowText(text)) => Some(text.as_ref()),
+            Some(HoverEvent::ShowEntity(either::Either::Left(entity))) => entity.name.as_deref(),
+            _ => None,
+        };
+        let separator = match self {
+            Self::Selector(selector) => selector.separator.as_deref(),
+            _ => None,
+        };
+        hover.into_iter().chain(separator)
+    }
+
+    /// Merges consecutive text children in `extra` that share the same style into one node.
+    /// Children carrying click or hover events are never merged
+    pub fn flatten(&mut self) {
+        self.for_each_mut(|component| merge_text_extras(&mut component.base_mut().extra))
+    }
+
+    /// Returns an equivalent tree where every node explicitly carries the style it inherits.
+    /// The root inherits no color and all decorations set to `false`; `font` is only set if some
+    /// ancestor sets it. Translatable arguments inherit the style of their translatable component.
+    /// Like vanilla, `insertion` and the click and hover events are inherited too
+    pub fn resolve_styles(&self) -> Component<'a> {
+        let mut resolved = self.clone();
+        resolved.inherit_styles(&BaseComponent::root_style());
+        resolved
+    }
+
+    fn inherit_styles(&mut self, parent: &BaseComponent<'a>) {
+        self.base_mut().inherit(parent);
+        let style = self.base().style();
+        self.for_each_child_mut(|child| child.inherit_styles(&style));
+    }
+
+    /// Removes every style field equal to the value the component inherits, the inverse of
+    /// [`Component::resolve_styles`]. The root inherits no color and all decorations set to
+    /// `false`, so only the fields that change how the tree is rendered are kept
+    pub fn optimize(&mut self) {
+        self.strip_inherited(&BaseComponent::root_style())
+    }
+
+    fn strip_inherited(&mut self, parent: &BaseComponent<'a>) {
+        let base = self.base_mut();
+        let mut style = base.style();
+        style.inherit(parent);
+        for (field, inherited) in [
+            (&mut base.bold, parent.bold),
+            (&mut base.italic, parent.italic),
+            (&mut base.underlined, parent.underlined),
+            (&mut base.strikethrough, parent.strikethrough),
+            (&mut base.obfuscated, parent.obfuscated),
+        ] {
+            if *field == inherited {
+                *field = None;
+            }
+        }
+        if base.font == parent.font {
+            base.font = None;
+        }
+        if base.color == parent.color {
+            base.color = None;
+        }
+        if base.shadow_color == parent.shadow_color {
+            base.shadow_color = None;
+        }
+        if base.insertion == parent.insertion {
+            base.insertion = None;
+        }
+        if base.click_event == parent.click_event {
+            base.click_event = None;
+        }
+        if base.hover_event == parent.hover_event {
+            base.hover_event = None;
+        }
+        if base.extra.is_empty() {
+            base.extra = Cow::Borrowed(&[]);
+        }
+        self.for_each_child_mut(|child| child.strip_inherited(&style));
+    }
+
+    /// Splits every text containing `http://` or `https://` links so that each link becomes its
+    /// own underlined child opening the link when clicked. A link ends at whitespace, trailing
+    /// punctuation is not part of it. Components with a click event are left as they are since
+    /// their children inherit it
+    pub fn linkify(&mut self) {
+        if self.base().click_event.is_some() {
+            return;
+        }
+        self.for_each_child_mut(Component::linkify);
+        let text = match self {
+            Component::Text(text) if text.text.contains("http") => text,
+            _ => return,
+        };
+        let pieces = split_links(&text.text);
+        if pieces.iter().all(|(_, link)| !link) {
+            return;
+        }
+        let children: Vec<Component<'a>> = pieces.into_iter()
+            .map(|(piece, link)| {
+                let mut piece = TextComponent::new(piece.to_string());
+                if link {
+                    piece.base.underlined = Some(true);
+                    piece.base.click_event = Some(ClickEvent::OpenUrl(Cow::Owned(piece.text.to_string())));
+                }
+                piece.into()
+            })
+            .collect();
+        text.text = Cow::Borrowed("");
+        let extra = std::mem::replace(&mut text.base.extra, Cow::Owned(children));
+        text.base.add_extras(extra);
+    }
+
+    /// Color the descendant at `path` is rendered with, its own or the closest ancestor's.
+    /// Every index of `path` selects an `extra` child, an empty path is this component.
+    /// `None` if the path does not exist or no color is set along it
+    pub fn color_at(&self, path: &[usize]) -> Option<Color<'a>> {
+        let mut component = self;
+        let mut color = component.base().color.as_ref();
+        for index in path {
+            component = component.base().extra.get(*index)?;
+            color = component.base().color.as_ref().or(color);
+        }
+        color.cloned()
+    }
+
+    /// Unsets every decoration set to `false` which would be `false` anyway, the opt-in
+    /// counterpart of serializing explicit values. A `false` overriding an inherited `true` is
+    /// kept. Unlike [`Component::optimize`] nothing else is touched
+    pub fn compact(&mut self) {
+        self.compact_inner(&BaseComponent::root_style())
+    }
+
+    fn compact_inner(&mut self, parent: &BaseComponent<'a>) {
+        let base = self.base_mut();
+        let mut style = base.style();
+        style.inherit(parent);
+        for (field, inherited) in [
+            (&mut base.bold, parent.bold),
+            (&mut base.italic, parent.italic),
+            (&mut base.underlined, parent.underlined),
+            (&mut base.strikethrough, parent.strikethrough),
+            (&mut base.obfuscated, parent.obfuscated),
+        ] {
+            if *field == Some(false) && inherited == Some(false) {
+                *field = None;
+            }
+        }
+        self.for_each_child_mut(|child| child.compact_inner(&style));
+    }
+
+    /// Sets `color` on this component and every descendant without a color.
+    /// Descendants of explicitly colored components keep inheriting that color
+    pub fn color_all(&mut self, color: Color<'a>) {
+        if self.base().color.is_some() {
+            return;
+        }
+        self.base_mut().color = Some(color.clone());
+        self.for_each_child_mut(|child| child.color_all(color.clone()));
+    }
+
+    /// Shifts the hue of every hex color in the tree by `degrees`, see [`HexColor::rotate_hue`].
+    /// Default colors are left as they are unless `convert_defaults` is set, then they are
+    /// rotated as their RGB value and become hex colors
+    pub fn rotate_hue(&mut self, degrees: f32, convert_defaults: bool) {
+        self.for_each_mut(|component| {
+            let color = &mut component.base_mut().color;
+            let rotated = match color {
+                Some(Color::Hex(hex)) => hex.rotate_hue(degrees),
+                Some(Color::Default(default)) if convert_defaults => default.to_hex_color().rotate_hue(degrees),
+                _ => return,
+            };
+            *color = Some(rotated.into());
+        });
+    }
+
+    /// Sets `event` as the click event of this component and of every descendant, so clicking any
+    /// part of the tree runs it. Without `overwrite` components that already have a click event
+    /// keep it and their descendants keep inheriting it, like with [`Component::color_all`]
+    pub fn set_click_all(&mut self, event: ClickEvent<'a>, overwrite: bool) {
+        if !overwrite && self.base().click_event.is_some() {
+            return;
+        }
+        self.base_mut().click_event = Some(event.clone());
+        self.for_each_child_mut(|child| child.set_click_all(event.clone(), overwrite));
+    }
+
+    /// Calls `f` on this component and all of its descendants in the same order as [`Component::iter`].
+    /// Children are visited after `f` returns, so `f` may rewrite them
+    pub fn for_each_mut(&mut self, mut f: impl FnMut(&mut Component<'a>)) {
+        self.for_each_mut_inner(&mut f)
+    }
+
+    fn for_each_mut_inner(&mut self, f: &mut impl FnMut(&mut Component<'a>)) {
+        f(self);
+        self.for_each_child_mut(|child| child.for_each_mut_inner(f));
+    }
+
+    /// Like [`Component::for_each_mut`], but also visits the components of
+    /// [`Component::payloads`] right after their component, in the order of
+    /// [`Component::iter_including_events`]
+    pub fn for_each_mut_including_events(&mut self, mut f: impl FnMut(&mut Component<'a>)) {
+        self.for_each_mut_including_events_inner(&mut f)
+    }
+
+    fn for_each_mut_including_events_inner(&mut self, f: &mut impl FnMut(&mut Component<'a>)) {
+        f(self);
+        self.for_each_payload_mut(|payload| payload.for_each_mut_including_events_inner(f));
+        self.for_each_child_mut(|child| child.for_each_mut_including_events_inner(f));
+    }
+
+    /// Calls `f` on the components of [`Component::payloads`]
+    pub(crate) fn for_each_payload_mut(&mut self, mut f: impl FnMut(&mut Component<'a>)) {
+        match &mut self.base_mut().hover_event {
+            Some(HoverEvent::ShowText(text)) => f(text),
+            Some(HoverEvent::ShowEntity(either::Either::Left(entity))) => {
+                if let Some(name) = &mut entity.name {
+                    f(name);
+                }
+            }
+            _ => {}
+        }
+        if let Self::Selector(selector) = self {
+            if let Some(separator) = &mut selector.separator {
+                f(separator);
+            }
+        }
+    }
+
+    /// Calls `f` on the translatable arguments and then on the `extra` children.
+    /// Borrowed children are only made owned if there are any
+    pub(crate) fn for_each_child_mut(&mut self, mut f: impl FnMut(&mut Component<'a>)) {
+        if let Self::Translatable(translatable) = self {
+            if !translatable.with.is_empty() {
+                translatable.with.to_mut().iter_mut().for_each(&mut f);
+            }
+        }
+        let extra = &mut self.base_mut().extra;
+        if !extra.is_empty() {
+            extra.to_mut().iter_mut().for_each(f);
+        }
+    }
+}
+
+/// Splits text into pieces, marking the ones which are links
+fn split_links(text: &str) -> Vec<(&str, bool)> {
+    let mut pieces = Vec::new();
+    let mut plain_start = 0;
+    let mut index = 0;
+    while let Some(start) = text[index..].find("http://").into_iter().chain(text[index..].find("https://")).min() {
+        let start = index + start;
+        let end = text[start..].find(char::is_whitespace).map_or(text.len(), |length| start + length);
+        let link = text[start..end].trim_end_matches(['.', ',', ';', ':', '!', '?', ')', '\'', '"']);
+        match link.ends_with("//") {
+            // The scheme alone is not a link
+            true => index = end,
+            false => {
+                if plain_start != start {
+                    pieces.push((&text[plain_start..start], false));
+                }
+                pieces.push((link, true));
+                index = start + link.len();
+                plain_start = index;
+            }
+        }
+    }
+    if plain_start != text.len() {
+        pieces.push((&text[plain_start..], false));
+    }
+    pieces
+}
+
+fn merge_text_extras<'a>(extra: &mut Cow<'a, [Component<'a>]>) {
+    let mergeable = extra.windows(2).any(|pair| match pair {
+        [Component::Text(last), Component::Text(next)] => last.can_merge(next),
+        _ => false,
+    });
+    if !mergeable {
+        return;
+    }
+    let mut merged: Vec<Component<'a>> = Vec::with_capacity(extra.len());
+    for child in std::mem::take(extra.to_mut()) {
+        match (merged.last_mut(), child) {
+            (Some(Component::Text(last)), Component::Text(next)) if last.can_merge(&next) => {
+                last.text.to_mut().push_str(&next.text);
+                last.base.extra = next.base.extra;
+            }
+            (_, child) => merged.push(child),
+        }
+    }
+    *extra = Cow::Owned(merged);
+}
+
+/// How [`Component::plain_text_with`] writes obfuscated text, which vanilla renders as random
+/// characters
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObfuscatedText {
+    /// The text as it is sent
+    Raw,
+    /// Every character replaced by this one, like `'█'`
+    Placeholder(char),
+}
+
+/// A piece of text with the fully resolved style it is displayed with, see [`Component::into_spans`].
+/// `style` never has `extra` children but carries the `insertion` and events the component has
+/// or inherits
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextSpan<'a> {
+    pub text: String,
+    pub style: BaseComponent<'a>,
+}
+
+/// Returns `true` if the limit was hit inside of `component`
+fn truncate_inner(component: &mut Component, remaining: &mut usize) -> bool {
+    let content = component.content();
+    if content.chars().count() > *remaining {
+        let text: String = match component {
+            Component::Text(_) => content.chars().take(*remaining).collect(),
+            _ => String::new(),
+        };
+        let base = BaseComponent {
+            extra: Cow::Owned(vec![TextComponent::new("…").into()]),
+            ..component.base().clone()
+        };
+        *component = TextComponent { text: Cow::Owned(text), base }.into();
+        return true;
+    }
+    *remaining -= content.chars().count();
+    if let Component::Translatable(translatable) = component {
+        if truncate_list(translatable.with.to_mut(), remaining) {
+            translatable.base.extra = Cow::Borrowed(&[]);
+            return true;
+        }
+    }
+    truncate_list(component.base_mut().extra.to_mut(), remaining)
+}
+
+fn truncate_list(components: &mut Vec<Component>, remaining: &mut usize) -> bool {
+    match components.iter_mut().position(|component| truncate_inner(component, remaining)) {
+        Some(index) => {
+            components.truncate(index + 1);
+            true
+        }
+        None => false,
+    }
+}
+
+pub struct Iter<'c, 'a> {
+    stack: Vec<&'c Component<'a>>,
+    /// Whether [`Component::payloads`] are visited too
+    payloads: bool,
+}
+
+impl<'c, 'a> Iterator for Iter<'c, 'a> {
+    type Item = &'c Component<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let component = self.stack.pop()?;
+        self.stack.extend(component.base().extra.iter().rev());
+        if let Component::Translatable(translatable) = component {
+            self.stack.extend(translatable.with.iter().rev());
+        }
+        if self.payloads {
+            let start = self.stack.len();
+            self.stack.extend(component.payloads());
+            self.stack[start..].reverse();
+        }
+        Some(component)
+    }
+}
+
+impl<'a> From<&'a str> for TextComponent<'a> {
+    fn from(text: &'a str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for TextComponent<'_> {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for TextComponent<'a> {
+    fn from(text: Cow<'a, str>) -> Self {
+        Self::new(text)
+    }
+}
+
+impl<'a> From<&'a str> for Component<'a> {
+    fn from(text: &'a str) -> Self {
+        Self::Text(text.into())
+    }
+}
+
+impl From<String> for Component<'_> {
+    fn from(text: String) -> Self {
+        Self::Text(text.into())
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for Component<'a> {
+    fn from(text: Cow<'a, str>) -> Self {
+        Self::Text(text.into())
+    }
+}
+
+/// Appends the right operand to the `extra` of the left one.
+/// An unstyled empty text component on the left is replaced by the right operand
+impl<'a, T: Into<Component<'a>>> Add<T> for Component<'a> {
+    type Output = Component<'a>;
+
+    fn add(mut self, rhs: T) -> Self::Output {
+        match &self {
+            Self::Text(text) if text.text.is_empty() && text.base == BaseComponent::empty() => rhs.into(),
+            _ => {
+                self.base_mut().add_extra(rhs);
+                self
+            }
+        }
+    }
+}
+
+impl<'a, T: Into<Component<'a>>> Add<T> for TextComponent<'a> {
+    type Output = Component<'a>;
+
+    fn add(self, rhs: T) -> Self::Output {
+        Component::from(self) + rhs
+    }
+}
+
+impl Default for BaseComponent<'_> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl Default for TextComponent<'_> {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl<'a> From<TextComponent<'a>> for Component<'a> {
+    fn from(component: TextComponent<'a>) -> Self {
+        Self::Text(component)
+    }
+}
+
+impl<'a> From<TranslatableComponent<'a>> for Component<'a> {
+    fn from(component: TranslatableComponent<'a>) -> Self {
+        Self::Translatable(component)
+    }
+}
+
+impl<'a> From<ScoreComponent<'a>> for Component<'a> {
+    fn from(component: ScoreComponent<'a>) -> Self {
+        Self::Score(component)
+    }
+}
+
+impl<'a> From<SelectorComponent<'a>> for Component<'a> {
+    fn from(component: SelectorComponent<'a>) -> Self {
+        Self::Selector(component)
+    }
+}
+
+impl<'a> From<KeyBindComponent<'a>> for Component<'a> {
+    fn from(component: KeyBindComponent<'a>) -> Self {
+        Self::KeyBind(component)
+    }
+}
+
+impl<'a> From<BaseComponent<'a>> for Component<'a> {
+    fn from(component: BaseComponent<'a>) -> Self {
+        Self::Base(component)
+    }
+}
+
+/// Serializes a [`Component`] as a string holding its json, for use with `#[serde(with = "...")]`.
+/// Older packets and some item data carry components this way
+///
+/// ```
+/// use bird_chat::component::{component_as_string, Component};
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Disconnect<'a> {
+///     #[serde(with = "component_as_string")]
+///     reason: Component<'a>,
+/// }
+///
+/// let json = serde_json::to_string(&Disconnect { reason: "Kicked".into() }).unwrap();
+/// assert_eq!(json, r#"{"reason":"{\"text\":\"Kicked\"}"}"#);
+/// let parsed: Disconnect = serde_json::from_str(&json).unwrap();
+/// assert_eq!(parsed.reason, Component::from("Kicked"));
+/// ```
+pub mod component_as_string {
+    use serde::{Deserialize, Serialize};
+    use super::Component;
+
+    pub fn serialize<S: serde::Serializer>(component: &Component, serializer: S) -> Result<S::Ok, S::Error> {
+        component.to_json().map_err(serde::ser::Error::custom)?.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, 'a, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Component<'a>, D::Error> {
+        let json = <std::borrow::Cow<str>>::deserialize(deserializer)?;
+        Component::from_json(&json).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serializes a [`Component`] as a json value, the same as its own `Serialize` implementation.
+/// Lets fields state the representation explicitly next to ones using [`component_as_string`]
+///
+/// ```
+/// use bird_chat::component::{component_as_value, Component};
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct SystemChat<'a> {
+///     #[serde(with = "component_as_value")]
+///     content: Component<'a>,
+///     overlay: bool,
+/// }
+///
+/// let json = serde_json::to_string(&SystemChat { content: "Hi".into(), overlay: false }).unwrap();
+/// assert_eq!(json, r#"{"content":{"text":"Hi"},"overlay":false}"#);
+/// let parsed: SystemChat = serde_json::from_str(&json).unwrap();
+/// assert_eq!(parsed.content, Component::from("Hi"));
+/// ```
+pub mod component_as_value {
+    use serde::{Deserialize, Serialize};
+    use super::Component;
+
+    pub fn serialize<S: serde::Serializer>(component: &Component, serializer: S) -> Result<S::Ok, S::Error> {
+        component.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, 'a, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Component<'a>, D::Error> {
+        Component::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(text: &str) -> TextComponent<'_> {
+        TextComponent::new(text)
+    }
+
+    #[test]
+    fn iter() {
+        let mut translatable = TranslatableComponent {
+            translate: Cow::Borrowed("chat.type.text"),
+            with: Cow::Borrowed(&[]),
+            base: BaseComponent::empty(),
+        };
+        translatable.add_arg(text("arg"));
+        translatable.base.add_extra(text("after"));
+        let mut root = text("root");
+        root.base.add_extra(translatable);
+        root.base.add_extra(text("last"));
+        let root = Component::from(root);
+        let visited: Vec<_> = root.iter()
+            .map(|component| match component {
+                Component::Text(text) => text.text.as_ref(),
+                Component::Translatable(translatable) => translatable.translate.as_ref(),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(visited, ["root", "chat.type.text", "arg", "after", "last"]);
+    }
+
+    #[test]
+    fn for_each_mut() {
+        let mut root = text("a");
+        root.base.add_extra(text("b"));
+        let mut root = Component::from(root);
+        root.for_each_mut(|component| component.base_mut().bold = Some(true));
+        assert!(root.iter().all(|component| component.base().bold == Some(true)));
+        assert_eq!(root.iter().count(), 2);
+    }
+
+    #[test]
+    fn flatten() {
+        let mut red = text("!");
+        red.base.color = Some(crate::formatting::DefaultColor::Red.into());
+        let mut root = text("");
+        root.base.add_extra(text("H"));
+        root.base.add_extra(text("i"));
+        root.base.add_extra(red);
+        let mut root = Component::from(root);
+        root.flatten();
+        match &root.base().extra[..] {
+            [Component::Text(merged), Component::Text(red)] => {
+                assert_eq!(merged.text, "Hi");
+                assert_eq!(red.text, "!");
+            }
+            extra => panic!("unexpected extra {:?}", extra),
+        }
+    }
+
+    #[test]
+    fn resolve_styles() {
+        let mut child = text("child");
+        child.base.italic = Some(true);
+        let mut root = text("root");
+        root.base.bold = Some(true);
+        root.base.color = Some(crate::formatting::DefaultColor::Red.into());
+        root.base.add_extra(child);
+        let resolved = Component::from(root).resolve_styles();
+        let root = resolved.base();
+        assert_eq!((root.bold, root.italic, root.underlined), (Some(true), Some(false), Some(false)));
+        let child = resolved.base().extra[0].base();
+        assert_eq!((child.bold, child.italic, child.obfuscated), (Some(true), Some(true), Some(false)));
+        assert_eq!(child.color, Some(crate::formatting::DefaultColor::Red.into()));
+        assert_eq!(child.font, None);
+
+        let link = Component::from(text("link ").on_click_url("https://example.com").with_insertion("x").with_extra("child"));
+        let resolved = link.resolve_styles();
+        let child = resolved.base().extra[0].base();
+        assert_eq!(child.click_event, link.base().click_event);
+        assert_eq!(child.insertion.as_deref(), Some("x"));
+        let mut optimized = resolved.clone();
+        optimized.optimize();
+        assert_eq!(optimized.base().extra[0].base().click_event, None);
+        assert_eq!(link.into_spans()[1].style.click_event, link.base().click_event);
+    }
+
+    #[test]
+    fn show_item() {
+        let hover = HoverEvent::ShowItem(either::Either::Left(ShowItemContents {
+            id: Identifier::new_fulled("minecraft:diamond").unwrap(),
+            count: Some(3),
+            tag: None,
+        }));
+        let json = serde_json::to_string(&hover).unwrap();
+        assert_eq!(json, r#"{"action":"show_item","contents":{"id":"minecraft:diamond","count":3}}"#);
+        assert_eq!(serde_json::from_str::<HoverEvent>(&json).unwrap(), hover);
+        assert_eq!(
+            serde_json::from_str::<HoverEvent>(r#"{"action":"show_item","value":{"id":"minecraft:diamond","count":3}}"#).unwrap(),
+            hover
+        );
+        let snbt = HoverEvent::ShowItem(either::Either::Right(Cow::Borrowed("{id:\"minecraft:stone\"}")));
+        assert_eq!(serde_json::to_string(&snbt).unwrap(), r#"{"action":"show_item","value":"{id:\"minecraft:stone\"}"}"#);
+        assert_eq!(
+            serde_json::from_str::<HoverEvent>(r#"{"action":"show_item","value":"{id:\"minecraft:stone\"}"}"#).unwrap(),
+            HoverEvent::ShowItem(either::Either::Right(Cow::Borrowed("{id:\"minecraft:stone\"}")))
+        );
+    }
+
+    #[test]
+    fn show_entity() {
+        let hover = HoverEvent::ShowEntity(either::Either::Left(ShowEntityContents {
+            entity_type: Identifier::new_fulled("minecraft:zombie").unwrap(),
+            id: Uuid::from_u128(0x1234),
+            name: Some(Box::new(text("Bob").into())),
+        }));
+        let json = serde_json::to_string(&hover).unwrap();
+        assert_eq!(
+            json,
+            r#"{"action":"show_entity","contents":{"type":"minecraft:zombie","id":"00000000-0000-0000-0000-000000001234","name":{"text":"Bob"}}}"#
+        );
+        assert_eq!(serde_json::from_str::<HoverEvent>(&json).unwrap(), hover);
+        let legacy = json.replace("contents", "value");
+        assert_eq!(serde_json::from_str::<HoverEvent>(&legacy).unwrap(), hover);
+        let snbt = HoverEvent::ShowEntity(either::Either::Right(Cow::Borrowed("{type:\"minecraft:pig\"}")));
+        assert_eq!(serde_json::to_string(&snbt).unwrap(), r#"{"action":"show_entity","value":"{type:\"minecraft:pig\"}"}"#);
+    }
+
+    #[test]
+    fn inline_click_events() {
+        let show_dialog = ClickEvent::ShowDialog(Identifier::new_fulled("minecraft:server_links").unwrap());
+        let json = serde_json::to_string(&show_dialog).unwrap();
+        assert_eq!(json, r#"{"action":"show_dialog","dialog":"minecraft:server_links"}"#);
+        assert_eq!(serde_json::from_str::<ClickEvent>(&json).unwrap(), show_dialog);
+
+        let custom = ClickEvent::Custom {
+            id: Identifier::new_fulled("example:ping").unwrap(),
+            payload: Some(Cow::Borrowed("pong")),
+        };
+        let json = serde_json::to_string(&custom).unwrap();
+        assert_eq!(json, r#"{"action":"custom","id":"example:ping","payload":"pong"}"#);
+        assert_eq!(serde_json::from_str::<ClickEvent>(&json).unwrap(), custom);
+
+        assert_eq!(
+            serde_json::from_str::<ClickEvent>(r#"{"action":"run_command","value":"/help"}"#).unwrap(),
+            ClickEvent::RunCommand(Cow::Borrowed("/help"))
+        );
+    }
+
+    #[test]
+    fn change_page() {
+        let page = ClickEvent::ChangePage(3);
+        assert_eq!(serde_json::to_string(&page).unwrap(), r#"{"action":"change_page","value":3}"#);
+        assert_eq!(serde_json::from_str::<ClickEvent>(r#"{"action":"change_page","value":3}"#).unwrap(), page);
+        assert_eq!(serde_json::from_str::<ClickEvent>(r#"{"action":"change_page","value":"3"}"#).unwrap(), page);
+        assert!(serde_json::from_str::<ClickEvent>(r#"{"action":"change_page","value":"three"}"#).is_err());
+    }
+
+    #[test]
+    fn shadow_color() {
+        let mut component = text("shadow");
+        assert_eq!(serde_json::to_string(&component).unwrap(), r#"{"text":"shadow"}"#);
+        component.base.shadow_color = Some(ShadowColor::Packed(-16777216));
+        let json = serde_json::to_string(&component).unwrap();
+        assert_eq!(json, r#"{"text":"shadow","shadow_color":-16777216}"#);
+        assert_eq!(serde_json::from_str::<TextComponent>(&json).unwrap(), component);
+        assert_eq!(
+            serde_json::from_str::<TextComponent>(r#"{"text":"shadow","shadow_color":[1.0,0.5,0.0,1.0]}"#).unwrap().base.shadow_color,
+            Some(ShadowColor::Rgba([1.0, 0.5, 0.0, 1.0]))
+        );
+    }
+
+    #[test]
+    fn deserialize_plain() {
+        assert_eq!(serde_json::from_str::<Component>("\"hi\"").unwrap(), Component::Text(text("hi")));
+        let mut parent = text("parent");
+        parent.base.add_extra(text("child"));
+        assert_eq!(
+            serde_json::from_str::<Component>(r#"{"text":"parent","extra":["child"]}"#).unwrap(),
+            Component::Text(parent)
+        );
+    }
+
+    #[test]
+    fn deserialize_list() {
+        let mut parent = text("hi ");
+        parent.base.add_extra(text(" there"));
+        assert_eq!(serde_json::from_str::<Component>(r#"["hi "," there"]"#).unwrap(), Component::Text(parent));
+        let mut nested = text("a");
+        nested.base.add_extra(text("b"));
+        let mut parent = text("root");
+        parent.base.add_extra(nested);
+        assert_eq!(serde_json::from_str::<Component>(r#"[{"text":"root"},["a","b"]]"#).unwrap(), Component::Text(parent));
+        assert!(serde_json::from_str::<Component>("[]").is_err());
+    }
+
+    #[test]
+    fn add() {
+        let sum = Component::from("a") + Component::from("b");
+        assert_eq!(sum.base().extra.len(), 1);
+        assert_eq!(Component::from("") + text("b"), Component::from("b"));
+        let chain = text("a") + "b" + "c";
+        assert_eq!(chain.base().extra.len(), 2);
+    }
+
+    #[test]
+    fn color_all() {
+        use crate::formatting::DefaultColor;
+        let mut blue = text("blue");
+        blue.base.color = Some(DefaultColor::Blue.into());
+        blue.base.add_extra(text("inherits blue"));
+        let mut root = text("root");
+        root.base.add_extra(blue);
+        root.base.add_extra(text("plain"));
+        let mut root = Component::from(root);
+        root.color_all(DefaultColor::Gold.into());
+        let extra = &root.base().extra;
+        assert_eq!(root.base().color, Some(DefaultColor::Gold.into()));
+        assert_eq!(extra[0].base().color, Some(DefaultColor::Blue.into()));
+        assert_eq!(extra[0].base().extra[0].base().color, None);
+        assert_eq!(extra[1].base().color, Some(DefaultColor::Gold.into()));
+    }
+
+    #[test]
+    fn resolve_translatable() {
+        let translations = HashMap::from([
+            ("chat.type.text".to_string(), "<%s> %s".to_string()),
+            ("swapped".to_string(), "%2$s then %1$s, 100%% %3$s".to_string()),
+        ]);
+        let mut translatable = TranslatableComponent {
+            translate: Cow::Borrowed("chat.type.text"),
+            with: Cow::Borrowed(&[]),
+            base: BaseComponent::empty(),
+        };
+        translatable.add_arg(text("Steve"));
+        translatable.add_arg(text("hello"));
+        let resolved = translatable.resolve(&translations);
+        assert_eq!(
+            resolved.base().extra.as_ref(),
+            [text("<").into(), text("Steve").into(), text("> ").into(), text("hello").into()]
+        );
+
+        translatable.translate = Cow::Borrowed("swapped");
+        let resolved = translatable.resolve(&translations);
+        assert_eq!(
+            resolved.base().extra.as_ref(),
+            [text("hello").into(), text(" then ").into(), text("Steve").into(), text(", 100% %3$s").into()]
+        );
+
+        translatable.translate = Cow::Borrowed("missing.key");
+        assert_eq!(translatable.resolve(&translations), Component::from("missing.key"));
+
+        let mut nested = TranslatableComponent::builder("swapped").build();
+        nested.add_arg(text("a"));
+        nested.add_arg(text("b"));
+        nested.add_arg(text("c"));
+        let mut outer = TranslatableComponent::builder("chat.type.text").build();
+        outer.add_arg(text("Steve").with_extra(nested.clone()));
+        outer.add_arg(text("hi"));
+        outer.base.add_extra(nested);
+        let resolved = outer.resolve(&translations);
+        assert!(resolved.iter().all(|component| !matches!(component, Component::Translatable(_))));
+        assert_eq!(resolved.plain_text(), "<Steveb then a, 100% c> hib then a, 100% c");
+    }
+
+    #[test]
+    fn wrap() {
+        use crate::formatting::DefaultColor;
+        let mut red = text("quick brown");
+        red.base.color = Some(DefaultColor::Red.into());
+        let mut root = text("The ");
+        root.base.bold = Some(true);
+        root.base.add_extra(red);
+        root.base.add_extra(text(" fox jumps supercalifragilistic"));
+        let lines = Component::from(root).wrap(10);
+        let plain: Vec<String> = lines.iter()
+            .map(|line| line.iter().map(|component| component.content().into_owned()).collect())
+            .collect();
+        assert_eq!(plain, ["The quick", "brown fox", "jumps", "supercalif", "ragilistic"]);
+        let first = &lines[0].base().extra;
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].content(), "The ");
+        assert_eq!(first[0].base().bold, Some(true));
+        assert_eq!(first[1].content(), "quick");
+        assert_eq!(first[1].base().color, Some(DefaultColor::Red.into()));
+        assert_eq!(first[1].base().bold, Some(true));
+        assert_eq!(lines[1].base().extra[0].base().color, Some(DefaultColor::Red.into()));
+        assert_eq!(lines[1].base().extra[1].base().color, None);
+    }
+
+    #[test]
+    fn default() {
+        let component = TextComponent {
+            text: Cow::Borrowed("hi"),
+            base: BaseComponent { bold: Some(true), ..Default::default() },
+        };
+        assert_eq!(serde_json::to_string(&component).unwrap(), r#"{"text":"hi","bold":true}"#);
+        assert_eq!(serde_json::to_string(&TextComponent::default()).unwrap(), r#"{"text":""}"#);
+    }
+
+    #[test]
+    fn gradient() {
+        let stops = [HexColor::new_rgb(255, 0, 0), HexColor::new_rgb(0, 255, 0), HexColor::new_rgb(0, 0, 255)];
+        let color = |component: &Component| match &component.base().color {
+            Some(Color::Hex(color)) => color.get_rgb(),
+            color => panic!("unexpected color {:?}", color),
+        };
+        let component = super::gradient("hello", &stops);
+        let extra = &component.base().extra;
+        assert_eq!(component.plain_text(), "hello");
+        assert_eq!(color(&extra[0]), (255, 0, 0));
+        assert_eq!(color(&extra[2]), (0, 255, 0));
+        assert_eq!(color(&extra[4]), (0, 0, 255));
+        assert_eq!(color(&super::gradient("a", &stops).base().extra[0]), (255, 0, 0));
+        assert_eq!(super::gradient("", &stops), Component::from(""));
+    }
+
+    #[test]
+    fn content_len() {
+        let mut red = text("héllo");
+        red.base.color = Some(crate::formatting::DefaultColor::Red.into());
+        red.base.bold = Some(true);
+        let component = text("> ") + (red + " wörld");
+        assert_eq!(component.content_len(), 13);
+        assert_eq!(component.content_len(), component.plain_text().chars().count());
+    }
+
+    #[test]
+    fn selector_separator() {
+        let selector = SelectorComponent {
+            selector: Cow::Borrowed("@a"),
+            separator: Some(Box::new(text(",").into())),
+            base: BaseComponent::empty(),
+        };
+        let json = r#"{"selector":"@a","separator":{"text":","}}"#;
+        assert_eq!(serde_json::to_string(&selector).unwrap(), json);
+        assert_eq!(serde_json::from_str::<Component>(json).unwrap(), Component::Selector(selector));
+        assert!(matches!(
+            serde_json::from_str::<Component>(r#"{"selector":"@p"}"#).unwrap(),
+            Component::Selector(SelectorComponent { separator: None, .. })
+        ));
+    }
+
+    #[test]
+    fn show_text() {
+        let legacy: HoverEvent = serde_json::from_str(r#"{"action":"show_text","value":"hi"}"#).unwrap();
+        let modern: HoverEvent = serde_json::from_str(r#"{"action":"show_text","value":{"text":"hi"}}"#).unwrap();
+        assert_eq!(legacy, modern);
+        assert_eq!(legacy, HoverEvent::ShowText(Box::new("hi".into())));
+        assert_eq!(serde_json::to_string(&legacy).unwrap(), r#"{"action":"show_text","value":{"text":"hi"}}"#);
+    }
+
+    #[test]
+    fn styled() {
+        let component = Component::from("run").on_click_command("/spawn").on_hover_text(text("Teleport"));
+        assert_eq!(
+            serde_json::to_string(&component).unwrap(),
+            r#"{"text":"run","clickEvent":{"action":"run_command","value":"/spawn"},"hoverEvent":{"action":"show_text","value":{"text":"Teleport"}}}"#
+        );
+        assert_eq!(text("id").on_copy("1234").base.click_event, Some(ClickEvent::CopyToClipboard(Cow::Borrowed("1234"))));
+    }
+
+    #[test]
+    fn empty() {
+        assert!(Component::empty().is_empty());
+        let mut bold = Component::empty();
+        bold.base_mut().bold = Some(true);
+        assert!((bold.clone() + "" + Component::empty()).is_empty());
+        assert!(!(bold + " ").is_empty());
+        assert!(!Component::from("hi").is_empty());
+    }
+
+    #[test]
+    fn optimize() {
+        let mut child = text("b");
+        child.base.bold = Some(false);
+        child.base.color = Some(crate::formatting::DefaultColor::Red.into());
+        child.base.italic = Some(true);
+        let mut root = text("a");
+        root.base.color = Some(crate::formatting::DefaultColor::Red.into());
+        root.base.underlined = Some(false);
+        let mut component = root + child;
+        let resolved = component.resolve_styles();
+        component.optimize();
+        assert_eq!(
+            serde_json::to_string(&component).unwrap(),
+            r#"{"text":"a","color":"red","extra":[{"text":"b","italic":true}]}"#
+        );
+        assert_eq!(component.resolve_styles(), resolved);
+    }
+
+    #[test]
+    fn legacy_color_code() {
+        let component: Component = serde_json::from_str(r#"{"text":"hi","color":"c"}"#).unwrap();
+        assert_eq!(component.base().color, Some(crate::formatting::DefaultColor::Red.into()));
+        let component: Component = serde_json::from_str(r#"{"text":"hi","color":"dark_cyan"}"#).unwrap();
+        assert_eq!(component.base().color, Some(crate::formatting::DefaultColor::DarkCyan.into()));
+        assert!(serde_json::from_str::<Component>(r#"{"text":"hi","color":"z"}"#).is_err());
+    }
+
+    #[test]
+    fn json_helpers() {
+        let component = text("a") + text("b");
+        let mut bytes = Vec::new();
+        component.to_writer(&mut bytes).unwrap();
+        assert_eq!(bytes, component.to_json().unwrap().into_bytes());
+        assert_eq!(Component::from_reader(bytes.as_slice()).unwrap(), component);
+        let mut rgb = text("rgb");
+        rgb.base.color = Some(crate::formatting::HexColor::new_rgb(1, 2, 3).into());
+        let rgb = Component::from(rgb);
+        assert_eq!(Component::from_json(&rgb.to_json().unwrap()).unwrap(), rgb);
+        assert!(Component::from_json("[]").is_err());
+        assert!(matches!(
+            Component::from_reader(br#"{"text":"a","color":"reddish"}"#.as_slice()),
+            Err(ComponentError::Color(ColorParseError::UnknownName(_)))
+        ));
+        assert!(matches!(Component::from_reader(br#"{"text":"#.as_slice()), Err(ComponentError::Json(_))));
+    }
+
+    #[test]
+    fn linkify() {
+        let mut component = Component::from("see http://x.com now");
+        component.linkify();
+        let extra = &component.base().extra;
+        assert_eq!(component.content(), "");
+        assert_eq!(extra.len(), 3);
+        assert_eq!(extra[0], Component::from("see "));
+        assert_eq!(extra[1].content(), "http://x.com");
+        assert_eq!(extra[1].base().underlined, Some(true));
+        assert_eq!(extra[1].base().click_event, Some(ClickEvent::OpenUrl(Cow::Borrowed("http://x.com"))));
+        assert_eq!(extra[2], Component::from(" now"));
+
+        let mut component = Component::from("(https://a.org/x?y=1), https:// and http://b.net.");
+        component.linkify();
+        let pieces: Vec<_> = component.base().extra.iter()
+            .map(|piece| (piece.content().into_owned(), piece.base().click_event.is_some()))
+            .collect();
+        assert_eq!(pieces, [
+            ("(".to_string(), false),
+            ("https://a.org/x?y=1".to_string(), true),
+            ("), https:// and ".to_string(), false),
+            ("http://b.net".to_string(), true),
+            (".".to_string(), false),
+        ]);
+        let mut plain = Component::from("no links");
+        plain.linkify();
+        assert_eq!(plain, Component::from("no links"));
+    }
+
+    #[test]
+    fn text_eq() {
+        let mut red = Component::from("hello");
+        red.base_mut().color = Some(crate::formatting::DefaultColor::Red.into());
+        let mut blue = text("hel") + "lo";
+        blue.base_mut().color = Some(crate::formatting::DefaultColor::Blue.into());
+        assert!(red.text_eq(&blue));
+        assert_ne!(red, blue);
+        assert!(!red.text_eq(&Component::from("hello!")));
+    }
+
+    #[test]
+    fn translatable_builder() {
+        let component = TranslatableComponent::builder("commands.give.success")
+            .args(["1", "Diamond"])
+            .arg(text("Steve"))
+            .on_hover_text("given")
+            .build();
+        assert_eq!(
+            serde_json::to_string(&component).unwrap(),
+            r#"{"translate":"commands.give.success","with":[{"text":"1"},{"text":"Diamond"},{"text":"Steve"}],"hoverEvent":{"action":"show_text","value":{"text":"given"}}}"#
+        );
+    }
+
+    #[test]
+    fn score_value() {
+        let mut score: Score = serde_json::from_str(r#"{"name":{"Left":"Steve"},"objective":"kills"}"#).unwrap();
+        assert_eq!(score.value_as_str(), None);
+        score.set_value("12");
+        assert_eq!(score.value_as_str(), Some("12"));
+        assert_eq!(score.value_as_i64(), Some(12));
+        assert_eq!(serde_json::to_string(&score).unwrap(), r#"{"name":{"Left":"Steve"},"objective":"kills","value":"12"}"#);
+        score.set_value(serde_json::Value::Null);
+        assert_eq!(serde_json::to_string(&score).unwrap(), r#"{"name":{"Left":"Steve"},"objective":"kills"}"#);
+    }
+
+    #[test]
+    fn events() {
+        let inner = text("b").on_click_command("/op me");
+        let outer = text("a").on_click_url("https://example.com").on_hover_text("link") + (text("c") + inner);
+        assert_eq!(outer.click_events(), [
+            &ClickEvent::OpenUrl(Cow::Borrowed("https://example.com")),
+            &ClickEvent::RunCommand(Cow::Borrowed("/op me")),
+        ]);
+        assert_eq!(outer.hover_events(), [&HoverEvent::ShowText(Box::new("link".into()))]);
+
+        let nested = text("hover").on_click_command("/hidden").on_hover_text("deeper");
+        let separator = text(", ").on_click_suggest("/sep");
+        let selector = SelectorComponent { selector: "@a".into(), separator: Some(Box::new(separator.into())), base: BaseComponent::empty() };
+        let outer = Component::from(text("a").on_hover_text(nested)) + selector;
+        assert_eq!(outer.click_events(), [
+            &ClickEvent::RunCommand(Cow::Borrowed("/hidden")),
+            &ClickEvent::SuggestCommand(Cow::Borrowed("/sep")),
+        ]);
+        assert_eq!(outer.hover_events().len(), 2);
+    }
+
+    #[test]
+    fn fonts() {
+        assert_eq!(DEFAULT_FONT.get_fulled(), "minecraft:default");
+        assert_eq!(DEFAULT_FONT.get_partial(), ("minecraft", "default"));
+        let component = text("glyphs").with_font(ALT_FONT);
+        assert_eq!(serde_json::to_string(&component).unwrap(), r#"{"text":"glyphs","font":"minecraft:alt"}"#);
+        assert_eq!(BaseComponent::empty().with_font(UNIFORM_FONT).font, Some(UNIFORM_FONT));
+    }
+
+    #[test]
+    fn insertion() {
+        let component = text("Steve").with_insertion("@p[name=Steve]");
+        assert_eq!(serde_json::to_string(&component).unwrap(), r#"{"text":"Steve","insertion":"@p[name=Steve]"}"#);
+        let mut base = BaseComponent::empty();
+        base.set_insertion("hi");
+        assert_eq!(base.insertion, Some(Cow::Borrowed("hi")));
+    }
+
+    #[test]
+    fn type_field() {
+        assert_eq!(Component::from_json(r#"{"type":"text","text":"x"}"#).unwrap(), Component::from("x"));
+        let component = Component::from_json(r#"{"type":"translatable","translate":"key","text":"fallback"}"#).unwrap();
+        assert!(matches!(component, Component::Translatable(_)));
+        let component = Component::from_json(r#"{"type":"keybind","keybind":"key.jump"}"#).unwrap();
+        assert!(matches!(component, Component::KeyBind(_)));
+        let component = Component::from_json(r#"{"text":"untyped","translate":"key"}"#).unwrap();
+        assert!(matches!(component, Component::Text(_)));
+    }
+
+    #[test]
+    fn score_builder() {
+        let uuid = Uuid::from_u128(1);
+        let component = ScoreComponent::builder("deaths").entity(uuid).value(3).with_insertion("x").build();
+        assert_eq!(component.score.name, either::Either::Right(uuid));
+        assert_eq!(component.score.value_as_i64(), Some(3));
+        assert_eq!(component.base.insertion, Some(Cow::Borrowed("x")));
+        assert_eq!(ScoreComponent::builder("deaths").build().score.name, either::Either::Left(Cow::Borrowed("*")));
+    }
+
+    #[test]
+    fn clear_styles() {
+        let mut component = text("a").on_click_url("https://example.com").on_hover_text("b").with_font(DEFAULT_FONT).with_insertion("c");
+        component.base.bold = Some(true);
+        component.base.color = Some(crate::formatting::DefaultColor::Red.into());
+        component.base.add_extra("child");
+        let mut base = component.base.clone();
+        base.clear_styles();
+        assert_eq!(base, BaseComponent { extra: Cow::Owned(vec!["child".into()]), ..BaseComponent::empty() });
+        base.reset();
+        assert_eq!(base, BaseComponent::empty());
+    }
+
+    fn chain(depth: usize) -> Component<'static> {
+        let mut component = Component::from("leaf");
+        for _ in 1..depth {
+            component = Component::empty() + text("node") + component;
+        }
+        component
+    }
+
+    /// Tree nesting only through hover texts
+    fn hover_chain(depth: usize) -> Component<'static> {
+        let mut component = Component::from("leaf");
+        for _ in 1..depth {
+            component = text("node").on_hover_text(component).into();
+        }
+        component
+    }
+
+    #[test]
+    fn depth() {
+        assert_eq!(Component::from("a").depth(), 1);
+        assert_eq!((text("a") + "b").depth(), 2);
+        let deep = chain(100);
+        assert_eq!(deep.depth(), 100);
+        assert_eq!(deep.validate_depth(64), Err(DepthError { max: 64 }));
+        assert_eq!(deep.validate_depth(100), Ok(()));
+        let hover = hover_chain(100);
+        assert_eq!(hover.depth(), 100);
+        assert_eq!(hover.validate_depth(64), Err(DepthError { max: 64 }));
+        let separator = SelectorComponent { selector: "@a".into(), separator: Some(Box::new(hover)), base: BaseComponent::empty() };
+        assert_eq!(Component::from(separator).depth(), 101);
+    }
+
+    #[test]
+    fn to_json_bounded() {
+        let deep = chain(2000);
+        assert!(matches!(deep.to_json_bounded(512), Err(BoundedJsonError::Depth(DepthError { max: 512 }))));
+        let shallow = chain(3);
+        assert_eq!(shallow.to_json_bounded(3).unwrap(), shallow.to_json().unwrap());
+        let hover = hover_chain(2000);
+        assert!(matches!(hover.to_json_bounded(512), Err(BoundedJsonError::Depth(DepthError { max: 512 }))));
+        assert_eq!(hover_chain(3).to_json_bounded(3).unwrap(), hover_chain(3).to_json().unwrap());
+    }
+
+    #[test]
+    fn with_extra() {
+        let component = TextComponent::from("a").with_extra("b").with_extra(text("c")).with_extra("d");
+        assert_eq!(Component::Text(component).plain_text(), "abcd");
+        assert_eq!(BaseComponent::empty().with_extra("x").extra.len(), 1);
+    }
+
+    #[test]
+    fn clone_keeps_borrows() {
+        static EXTRA: [Component; 1] = [Component::Text(TextComponent { text: Cow::Borrowed("b"), base: BaseComponent::empty() })];
+        let text = "a";
+        let component = Component::Text(TextComponent {
+            text: Cow::Borrowed(text),
+            base: BaseComponent { extra: Cow::Borrowed(&EXTRA), ..BaseComponent::empty() },
+        });
+        let cloned = component.clone();
+        match &cloned {
+            Component::Text(TextComponent { text: Cow::Borrowed(cloned), base }) => {
+                assert!(std::ptr::eq(*cloned, text));
+                assert!(matches!(&base.extra, Cow::Borrowed(extra) if std::ptr::eq(*extra, &EXTRA[..])));
+            }
+            cloned => panic!("clone allocated: {:?}", cloned),
+        }
+    }
+
+    #[test]
+    fn color_at() {
+        use crate::formatting::DefaultColor;
+        let mut gold = text("gold");
+        gold.base.color = Some(DefaultColor::Gold.into());
+        let mut red = text("red");
+        red.base.color = Some(DefaultColor::Red.into());
+        let component = text("root") + (gold + text("inherits")) + (red + (text("nested") + "deep"));
+        assert_eq!(component.color_at(&[]), None);
+        assert_eq!(component.color_at(&[0, 0]), Some(DefaultColor::Gold.into()));
+        assert_eq!(component.color_at(&[1, 0, 0]), Some(DefaultColor::Red.into()));
+        assert_eq!(component.color_at(&[2]), None);
+        assert_eq!(component.color_at(&[0, 1]), None);
+    }
+
+    #[test]
+    fn args() {
+        let mut component = TranslatableComponent::builder("chat.type.text").args(["Steve", "hi"]).with_extra("!").build();
+        assert_eq!(component.args(), [Component::from("Steve"), Component::from("hi")]);
+        assert_eq!(component.base.extra.as_ref(), [Component::from("!")]);
+        component.add_arg("more");
+        assert_eq!(component.args().len(), 3);
+        assert_eq!(component.base.extra.len(), 1);
+    }
+
+    #[test]
+    fn overlay() {
+        use crate::formatting::DefaultColor;
+        let mut base = BaseComponent { bold: Some(true), color: Some(DefaultColor::Gray.into()), ..BaseComponent::empty() }.with_extra("a");
+        let theme = BaseComponent { color: Some(DefaultColor::Gold.into()), italic: Some(false), ..BaseComponent::empty() }.with_extra("b");
+        base.overlay(&theme);
+        assert_eq!(base.bold, Some(true));
+        assert_eq!(base.italic, Some(false));
+        assert_eq!(base.color, Some(DefaultColor::Gold.into()));
+        assert_eq!(base.extra.as_ref(), [Component::from("a"), Component::from("b")]);
+    }
+
+    #[test]
+    fn compact() {
+        let mut child = text("b");
+        child.base.bold = Some(false);
+        let mut bold = text("c");
+        bold.base.bold = Some(true);
+        let mut override_bold = text("d");
+        override_bold.base.bold = Some(false);
+        let mut root = text("a");
+        root.base.bold = Some(false);
+        root.base.italic = Some(true);
+        let component = root + child + (bold + override_bold);
+        assert_eq!(
+            component.to_json_compact().unwrap(),
+            r#"{"text":"a","italic":true,"extra":[{"text":"b"},{"text":"c","bold":true,"extra":[{"text":"d","bold":false}]}]}"#
+        );
+        assert!(component.to_json().unwrap().starts_with(r#"{"text":"a","bold":false"#));
+    }
+
+    #[test]
+    fn rainbow() {
+        use crate::formatting::DefaultColor;
+        let component = super::rainbow("Hello, world");
+        let extra = &component.base().extra;
+        assert_eq!(extra.len(), 12);
+        assert_eq!(component.plain_text(), "Hello, world");
+        let colors: Vec<_> = extra.iter().map(|c| c.base().color.clone().unwrap()).collect();
+        assert_eq!(colors[0], DefaultColor::Red.into());
+        assert_eq!(colors[6], DefaultColor::Pink.into());
+        assert_eq!(colors[7], DefaultColor::Pink.into());
+        assert_eq!(colors[8], DefaultColor::Red.into());
+    }
+
+    #[test]
+    fn network_string() {
+        let component = text("say \"hi\"").with_extra("!");
+        let string = Component::from(component).to_network_string().unwrap();
+        assert_eq!(string, r#""{\"text\":\"say \\\"hi\\\"\",\"extra\":[{\"text\":\"!\"}]}""#);
+        assert_eq!(Component::from_network_string(&string).unwrap().plain_text(), "say \"hi\"!");
+        assert!(Component::from_network_string(r#"{"text":"not a string"}"#).is_err());
+    }
+
+    #[test]
+    fn empty_constant() {
+        fn message() -> &'static Component<'static> {
+            &EMPTY_COMPONENT
+        }
+        assert_eq!(message().to_json().unwrap(), r#"{"text":""}"#);
+        assert_eq!(Component::EMPTY, Component::empty());
+    }
+
+    #[test]
+    fn iter_including_events() {
+        let mut hover = text("inside");
+        hover.base.add_extra(text("nested"));
+        let mut child = text("child");
+        child.base.hover_event = Some(HoverEvent::ShowText(Box::new(hover.into())));
+        let mut root = text("root");
+        root.base.add_extra(child);
+        root.base.add_extra(text("last"));
+        let root = Component::from(root);
+        let texts = |iter: Iter| iter.map(|component| component.content().into_owned()).collect::<Vec<_>>();
+        assert_eq!(texts(root.iter()), ["root", "child", "last"]);
+        assert_eq!(texts(root.iter_including_events()), ["root", "child", "inside", "nested", "last"]);
+
+        let shared = [Component::from("same")];
+        let mut twice = text("");
+        twice.base.add_extras(&shared[..]);
+        twice.base.add_extras(&shared[..]);
+        let selector = SelectorComponent { selector: "@a".into(), separator: Some(Box::new(twice.into())), base: BaseComponent::empty() };
+        let selector = Component::from(selector);
+        assert_eq!(texts(selector.iter_including_events()), ["@a", "", "same", "same"]);
+    }
+
+    #[test]
+    fn truncate() {
+        let mut name = text("Steve");
+        name.base.color = Some(crate::formatting::DefaultColor::Gold.into());
+        let mut root = text("Hello, ");
+        root.base.color = Some(crate::formatting::DefaultColor::Gray.into());
+        root.base.add_extra(name);
+        root.base.add_extra(text(" how are you?"));
+        let root = Component::from(root);
+        assert_eq!(root.truncate(100), root);
+        assert_eq!(
+            root.truncate(10).to_json().unwrap(),
+            r#"{"text":"Hello, ","color":"gray","extra":[{"text":"Ste","color":"gold","extra":[{"text":"…"}]}]}"#
+        );
+        assert_eq!(root.truncate(10).plain_text(), "Hello, Ste…");
+        let mut translatable = TranslatableComponent::builder("chat.type.text").build();
+        translatable.add_arg(text("Steve"));
+        let root = Component::from(text("> ").with_extra(translatable));
+        assert_eq!(root.truncate(4).plain_text(), "> …");
+        assert_eq!(root.truncate(100), root);
+    }
+
+    #[test]
+    fn into_spans() {
+        use crate::formatting::DefaultColor;
+        let mut inner = text("world");
+        inner.base.bold = Some(true);
+        let mut root = text("hello ");
+        root.base.color = Some(DefaultColor::Red.into());
+        root.base.add_extra(inner);
+        root.base.add_extra(text(""));
+        let spans = Component::from(root).into_spans();
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].text, "hello ");
+        assert_eq!(spans[0].style.color, Some(DefaultColor::Red.into()));
+        assert_eq!(spans[0].style.bold, Some(false));
+        assert_eq!(spans[1].text, "world");
+        assert_eq!(spans[1].style.color, Some(DefaultColor::Red.into()));
+        assert_eq!(spans[1].style.bold, Some(true));
+        assert_eq!(spans[1].style.italic, Some(false));
+    }
+
+    #[test]
+    fn scalar_args() {
+        let component = Component::from_json(r#"{"translate":"k","with":[5,"name",{"text":"x","bold":true},2.5,true]}"#).unwrap();
+        let Component::Translatable(translatable) = &component else { unreachable!() };
+        let args: Vec<_> = translatable.with.iter().map(Component::plain_text).collect();
+        assert_eq!(args, ["5", "name", "x", "2.5", "true"]);
+        assert_eq!(translatable.with[0], Component::from("5"));
+        assert_eq!(translatable.with[2].base().bold, Some(true));
+    }
+
+    #[test]
+    fn set_click_all() {
+        let hint = ClickEvent::SuggestCommand(Cow::Borrowed("/hint"));
+        let mut own = text("own");
+        own.base.click_event = Some(hint.clone());
+        own.base.add_extra(text("inner"));
+        let mut root = text("root");
+        root.base.add_extra(text("a"));
+        root.base.add_extra(own);
+        let root = Component::from(root);
+        let event = ClickEvent::RunCommand(Cow::Borrowed("/x"));
+        let events = |component: &Component<'static>| component.iter().map(|component| component.base().click_event.clone()).collect::<Vec<_>>();
+
+        let mut kept = root.clone();
+        kept.set_click_all(event.clone(), false);
+        assert_eq!(events(&kept), [Some(event.clone()), Some(event.clone()), Some(hint), None]);
+
+        let mut overwritten = root;
+        overwritten.set_click_all(event.clone(), true);
+        assert_eq!(events(&overwritten), vec![Some(event); 4]);
+    }
+
+    #[test]
+    fn resolve_selector() {
+        let selector = SelectorComponent { selector: Cow::Borrowed("@a"), separator: None, base: BaseComponent::empty() };
+        assert_eq!(Component::from(selector.clone()).plain_text(), "@a");
+        let resolved = selector.resolve(["Alex", "Steve"]);
+        assert_eq!(resolved.plain_text(), "Alex, Steve");
+        assert_eq!(resolved.base().extra[1].base().color, Some(DefaultColor::Gray.into()));
+
+        let custom = SelectorComponent { separator: Some(Box::new(text(" | ").into())), ..selector };
+        assert_eq!(custom.resolve(["Alex", "Steve", "Notch"]).plain_text(), "Alex | Steve | Notch");
+        assert_eq!(custom.resolve(Vec::<Component>::new()).plain_text(), "");
+    }
+
+    #[test]
+    fn component_errors() {
+        let error = |json| Component::from_json(json).unwrap_err();
+        assert!(matches!(
+            error(r##"{"text":"a","extra":[{"text":"b","color":"#12345z"}]}"##),
+            ComponentError::Color(ColorParseError::Hex(crate::formatting::HexColorError::HexValueContainsBadCharacters))
+        ));
+        assert!(matches!(error(r#"{"text":"a","color":"reddish"}"#), ComponentError::Color(ColorParseError::UnknownName(_))));
+        assert!(matches!(error(r#"{"text":"a","font":"a:b:c"}"#), ComponentError::Identifier(_)));
+        assert!(matches!(
+            error(r#"{"text":"a","clickEvent":{"action":"explode","value":""}}"#),
+            ComponentError::UnknownAction(action) if action == "explode"
+        ));
+        assert!(matches!(
+            error(r#"{"text":"a","hover_event":{"action":"show_achievement","value":"a"}}"#),
+            ComponentError::UnknownAction(action) if action == "show_achievement"
+        ));
+        assert!(matches!(error(r#"{"text":"a""#), ComponentError::Json(_)));
+        assert!(matches!(error(r#"{"text":"a","color":"red","bold":"yes"}"#), ComponentError::Json(_)));
+    }
+
+    #[test]
+    fn format_components() {
+        let player = Component::from(text("Steve").with_color(DefaultColor::Yellow));
+        let joined = super::format_components("{} joined", std::slice::from_ref(&player));
+        assert_eq!(joined.base().extra.as_ref(), [player.clone(), text(" joined").into()]);
+        let formatted = super::format_components("{{{}}} {} {", std::slice::from_ref(&player));
+        assert_eq!(formatted.plain_text(), "{Steve} {} {");
+        assert_eq!(super::format_components("} {}", std::slice::from_ref(&player)).plain_text(), "} Steve");
+        assert_eq!(super::format_components("{x} {}", &[player]).plain_text(), "{x} Steve");
+    }
+
+    #[test]
+    fn plain_text_obfuscated() {
+        let mut secret = text("secret");
+        secret.base.obfuscated = Some(true);
+        secret.base.add_extra(text("!"));
+        let mut root = text("code: ");
+        root.base.add_extra(secret);
+        root.base.add_extra(text(" end"));
+        let root = Component::from(root);
+        assert_eq!(root.plain_text_with(ObfuscatedText::Raw), root.plain_text());
+        assert_eq!(root.plain_text_with(ObfuscatedText::Placeholder('█')), "code: ███████ end");
+    }
+
+    #[test]
+    fn join_and_newline() {
+        let lines = ["first", "second", "third"].map(Component::from);
+        let joined = super::join(lines, &Component::from("\n"));
+        assert_eq!(joined.plain_text(), "first\nsecond\nthird");
+        assert_eq!(joined.base().extra.len(), 5);
+        assert_eq!(super::join([], &Component::from("\n")).plain_text(), "");
+
+        let mut message = Component::from("title");
+        message.append_newline();
+        message.base_mut().add_extra(text("body"));
+        assert_eq!(message.plain_text(), "title\nbody");
+    }
+
+    #[test]
+    fn rotate_hue() {
+        let red = HexColor::new_rgb(255, 0, 0);
+        let mut root = Component::from(text("red").with_color(red));
+        root.base_mut().add_extra(text("gold").with_color(DefaultColor::Gold));
+        let mut rotated = root.clone();
+        rotated.rotate_hue(120.0, false);
+        assert_eq!(rotated.base().color, Some(HexColor::new_rgb(0, 255, 0).into()));
+        assert_eq!(rotated.base().extra[0].base().color, Some(DefaultColor::Gold.into()));
+        root.rotate_hue(-120.0, true);
+        assert_eq!(root.base().color, Some(HexColor::new_rgb(0, 0, 255).into()));
+        assert!(matches!(root.base().extra[0].base().color, Some(Color::Hex(_))));
+    }
+
+    #[test]
+    fn base_round_trip() {
+        let base = BaseComponent::builder()
+            .with_color(DefaultColor::Red)
+            .extra("a")
+            .extra(text("b").with_color(DefaultColor::Blue))
+            .build();
+        let json = base.to_json().unwrap();
+        assert_eq!(json, r#"{"text":"","color":"red","extra":[{"text":"a"},{"text":"b","color":"blue"}]}"#);
+        assert_eq!(Component::from_json(&json).unwrap(), Component::from(TextComponent { text: "".into(), base: base.base().clone() }));
+        assert!(matches!(Component::from_json(r#"{"color":"red"}"#).unwrap(), Component::Base(_)));
+        assert!(matches!(Component::from_json("{}").unwrap(), Component::Base(_)));
+        assert_eq!(Component::Base(BaseComponent::empty()).to_json().unwrap(), r#"{"text":""}"#);
+        assert!(matches!(Component::from_json(r#"{"text":"","color":"red"}"#).unwrap(), Component::Text(_)));
+    }
+
+    #[test]
+    fn text_cmp() {
+        use std::cmp::Ordering;
+        let mut entries = [Component::from("banana"), text("app").with_extra("le").into(), Component::from("cherry")];
+        entries.sort_by(Component::text_cmp);
+        let sorted: Vec<_> = entries.iter().map(Component::plain_text).collect();
+        assert_eq!(sorted, ["apple", "banana", "cherry"]);
+        assert_eq!(Component::from("a").text_cmp(&text("a").with_color(DefaultColor::Red).into()), Ordering::Equal);
+    }
+
+    #[test]
+    fn normalize_score_name() {
+        let uuid = "069a79f4-44e9-4726-a5be-fca90e38aaf5";
+        let mut score = ScoreComponent::builder("kills").player(uuid).build().score;
+        score.normalize();
+        assert_eq!(score.name, either::Either::Right(Uuid::parse_str(uuid).unwrap()));
+        let mut player = ScoreComponent::builder("kills").player("Notch").build().score;
+        player.normalize();
+        assert_eq!(player.name, either::Either::Left(Cow::Borrowed("Notch")));
+    }
+}
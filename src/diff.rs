@@ -0,0 +1,130 @@
+use std::mem::discriminant;
+use crate::component::Component;
+
+/// What changed between two trees, see [`diff`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComponentDiff {
+    /// Changed nodes in depth-first order, parents before their children
+    pub changes: Vec<NodeChange>,
+}
+
+/// A node present in both trees which differs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeChange {
+    /// Indices into the children at every level, translatable arguments before `extra`.
+    /// Empty for the root
+    pub path: Vec<usize>,
+    pub fields: ChangedFields,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChangedFields {
+    /// The kind of component or its own text, translation key, key bind, score or selector,
+    /// including the score holder, objective and selector separator
+    pub content: bool,
+    /// Decorations, color, font, shadow color or insertion
+    pub style: bool,
+    /// Click or hover event
+    pub events: bool,
+    /// Number of children. Children present in both trees are compared on their own
+    pub children: bool,
+}
+
+impl ChangedFields {
+    pub const fn any(&self) -> bool {
+        self.content || self.style || self.events || self.children
+    }
+}
+
+impl ComponentDiff {
+    /// Whether both trees render the same
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Compares both trees node by node. Children are matched by position, so inserting a child
+/// reports every following child as changed
+pub fn diff<'a>(old: &Component<'a>, new: &Component<'a>) -> ComponentDiff {
+    let mut diff = ComponentDiff::default();
+    diff_node(old, new, &mut Vec::new(), &mut diff.changes);
+    diff
+}
+
+fn diff_node<'a>(old: &Component<'a>, new: &Component<'a>, path: &mut Vec<usize>, changes: &mut Vec<NodeChange>) {
+    let (old_base, new_base) = (old.base(), new.base());
+    let fields = ChangedFields {
+        content: !same_content(old, new),
+        style: !old_base.same_style(new_base),
+        events: old_base.click_event != new_base.click_event || old_base.hover_event != new_base.hover_event,
+        children: old.children().count() != new.children().count(),
+    };
+    if fields.any() {
+        changes.push(NodeChange { path: path.clone(), fields });
+    }
+    for (index, (old, new)) in old.children().zip(new.children()).enumerate() {
+        path.push(index);
+        diff_node(old, new, path, changes);
+        path.pop();
+    }
+}
+
+fn same_content<'a>(old: &Component<'a>, new: &Component<'a>) -> bool {
+    match (old, new) {
+        (Component::Score(old), Component::Score(new)) => old.score == new.score,
+        (Component::Selector(old), Component::Selector(new)) =>
+            old.selector == new.selector && old.separator == new.separator,
+        _ => discriminant(old) == discriminant(new) && old.content() == new.content(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::{BaseComponent, ScoreComponent, SelectorComponent, TextComponent};
+    use crate::formatting::DefaultColor;
+    use super::*;
+
+    #[test]
+    fn color_change() {
+        let old = TextComponent::new("Score: ") + TextComponent::new("10");
+        let mut new = old.clone();
+        assert!(diff(&old, &new).is_empty());
+        new.for_each_mut(|component| if component.content() == "10" {
+            component.base_mut().color = Some(DefaultColor::Gold.into());
+        });
+        assert_eq!(diff(&old, &new).changes, [NodeChange {
+            path: vec![0],
+            fields: ChangedFields { style: true, ..ChangedFields::default() },
+        }]);
+    }
+
+    #[test]
+    fn content_and_children() {
+        let old = TextComponent::new("a") + "b";
+        let new = TextComponent::new("c") + "b" + "d";
+        assert_eq!(diff(&old, &new).changes, [NodeChange {
+            path: vec![],
+            fields: ChangedFields { content: true, children: true, ..ChangedFields::default() },
+        }]);
+    }
+
+    #[test]
+    fn score_and_separator() {
+        let old = Component::from(ScoreComponent::builder("kills").player("Steve").build());
+        let new = Component::from(ScoreComponent::builder("deaths").player("Steve").build());
+        assert_eq!(diff(&old, &new).changes, [NodeChange {
+            path: vec![],
+            fields: ChangedFields { content: true, ..ChangedFields::default() },
+        }]);
+        let selector = |separator: &'static str| Component::from(SelectorComponent {
+            selector: "@a".into(),
+            separator: Some(Box::new(TextComponent::new(separator).into())),
+            base: BaseComponent::empty(),
+        });
+        assert!(diff(&selector(", "), &selector(", ")).is_empty());
+        assert_eq!(diff(&selector(", "), &selector(" | ")).changes, [NodeChange {
+            path: vec![],
+            fields: ChangedFields { content: true, ..ChangedFields::default() },
+        }]);
+    }
+}
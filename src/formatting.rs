@@ -13,12 +13,30 @@ pub enum Decoration {
     Italic,
 }
 
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
-#[serde(try_from = "String", into = "String")]
+/// Serialized as `#rrggbb`, see [`as_array`] for the `[r, g, b]` form.
+/// Both forms are accepted when deserializing. Colors are compared and hashed by their
+/// channels, so a color built from rgb equals the same color built from hex
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "HexColorRepr", into = "String")]
 pub struct HexColor<'a>(HexColorInner<'a>);
 
-#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "snake_case")]
+impl PartialEq for HexColor<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.get_rgb() == other.get_rgb()
+    }
+}
+
+impl Eq for HexColor<'_> {}
+
+impl std::hash::Hash for HexColor<'_> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.get_rgb().hash(state)
+    }
+}
+
+/// Serialized as its vanilla name, see [`DefaultColor::name`]. Deserializes from anything
+/// [`DefaultColor::from_name`] accepts or from a legacy formatting code like `"c"`
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DefaultColor {
     Black,
     DarkBlue,
@@ -45,6 +63,16 @@ pub enum Color<'a> {
     Hex(HexColor<'a>),
 }
 
+/// Color of the text shadow, added in 1.21.4
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum ShadowColor {
+    /// ARGB packed into a single integer
+    Packed(i32),
+    /// RGBA channels in `0.0..=1.0`
+    Rgba([f32; 4]),
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
 pub enum HexColorError {
     #[error("Hex value contains bad characters")]
@@ -55,6 +83,35 @@ pub enum HexColorError {
     HexValueTooSmall,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ColorParseError {
+    #[error("Unknown color name {0:?}")]
+    UnknownName(String),
+    #[error(transparent)]
+    Hex(#[from] HexColorError),
+}
+
+impl<'a> Color<'a> {
+    pub fn into_owned(self) -> Color<'static> {
+        match self {
+            Self::Default(color) => Color::Default(color),
+            Self::Hex(color) => Color::Hex(color.into_owned()),
+        }
+    }
+
+    /// Parses a color name as [`DefaultColor::from_name`] does, or a hex color otherwise
+    pub fn parse(color: &'a str) -> Result<Self, ColorParseError> {
+        if let Some(default) = DefaultColor::from_name(color) {
+            return Ok(Self::Default(default));
+        }
+        match HexColor::new_hex(color) {
+            Ok(hex) => Ok(Self::Hex(hex)),
+            Err(error) if color.starts_with('#') => Err(error.into()),
+            Err(_) => Err(ColorParseError::UnknownName(color.to_string())),
+        }
+    }
+}
+
 impl<'a> HexColor<'a> {
     const fn new(inner: HexColorInner<'a>) -> Self {
         Self(inner)
@@ -68,21 +125,20 @@ impl<'a> HexColor<'a> {
         Self::new(either::Either::Left((r, g, b)))
     }
 
+    /// Accepts `#rrggbb` and `rrggbb` in any case. The value is always stored as lowercase
+    /// `#rrggbb`, so [`HexColor::get_hex`] returns it in that form
     pub fn new_hex(hex: impl Into<Cow<'a, str>>) -> Result<Self, HexColorError> {
         let hex = hex.into();
-        match hex.len().cmp(&7) {
-            Ordering::Less => Err(HexColorError::HexValueTooLong),
+        let digits = hex.strip_prefix('#').unwrap_or(&hex);
+        match digits.len().cmp(&6) {
+            Ordering::Less => Err(HexColorError::HexValueTooSmall),
             Ordering::Greater => Err(HexColorError::HexValueTooLong),
-            // Safety. The length is 7 so next will get first element, which is exist
-            Ordering::Equal => match unsafe { hex.chars().next().unwrap_unchecked() } == '#' &&
-                hex[1..=7].contains(|c: char| {
-                    match c {
-                        '0'..='9' | 'a'..='f' | 'A'..='F' => false,
-                        _ => true
-                    }
-                }) {
+            Ordering::Equal => match digits.contains(|c: char| !c.is_ascii_hexdigit()) {
                 true => Err(HexColorError::HexValueContainsBadCharacters),
-                false => Ok(Self::new(HexColorInner::Right(hex)))
+                false => match hex.starts_with('#') && !digits.contains(|c: char| c.is_ascii_uppercase()) {
+                    true => Ok(Self::new(HexColorInner::Right(hex))),
+                    false => Ok(Self::new(HexColorInner::Right(Cow::Owned(format!("#{}", digits.to_ascii_lowercase()))))),
+                }
             }
         }
     }
@@ -90,7 +146,7 @@ impl<'a> HexColor<'a> {
     pub fn get_rgb(&self) -> (u8, u8, u8) {
         match self.get() {
             HexColorInner::Left((r, g, b)) => (*r, *g, *b),
-            // Safety. Guarantied by constructors
+            // Safety. Constructors store exactly `#` followed by six hex digits
             HexColorInner::Right(str) => unsafe {
                 (
                     u8::from_str_radix(&str[1..3], 16).unwrap_unchecked(),
@@ -101,21 +157,335 @@ impl<'a> HexColor<'a> {
         }
     }
 
+    pub fn into_owned(self) -> HexColor<'static> {
+        HexColor::new(match self.0 {
+            HexColorInner::Left(rgb) => HexColorInner::Left(rgb),
+            HexColorInner::Right(hex) => HexColorInner::Right(Cow::Owned(hex.into_owned())),
+        })
+    }
+
     pub fn get_hex(&'a self) -> Cow<'a, str> {
         match self.get() {
             HexColorInner::Left((r, g, b)) =>
                 Cow::Owned(format!("#{:02x}{:02x}{:02x}", r, g, b)),
-            HexColorInner::Right(str) => Cow::Borrowed(&str)
+            HexColorInner::Right(str) => Cow::Borrowed(str)
         }
     }
 }
 
+impl HexColor<'_> {
+    /// `#RRGGBB` with uppercase digits, for tools which do not accept lowercase ones
+    pub fn to_hex_upper(&self) -> String {
+        let (r, g, b) = self.get_rgb();
+        format!("#{:02X}{:02X}{:02X}", r, g, b)
+    }
+
+    /// Linearly interpolates every channel, `t` is clamped to `0.0..=1.0`
+    pub fn lerp(&self, other: &HexColor, t: f32) -> HexColor<'static> {
+        let t = t.clamp(0.0, 1.0);
+        let (from, to) = (self.get_rgb(), other.get_rgb());
+        let channel = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * t).round() as u8;
+        HexColor::new_rgb(channel(from.0, to.0), channel(from.1, to.1), channel(from.2, to.2))
+    }
+
+    /// Moves every channel towards black by `factor` in RGB space, `factor` is clamped to `0.0..=1.0`
+    pub fn darken(&self, factor: f32) -> HexColor<'static> {
+        self.lerp(&HexColor::new_rgb(0, 0, 0), factor)
+    }
+
+    /// Moves every channel towards white by `factor` in RGB space, `factor` is clamped to `0.0..=1.0`
+    pub fn lighten(&self, factor: f32) -> HexColor<'static> {
+        self.lerp(&HexColor::new_rgb(255, 255, 255), factor)
+    }
+
+    /// Scales the channels so the brightest one, the HSV value, becomes `value`.
+    /// `value` is clamped to `0.0..=1.0`, black becomes gray
+    pub fn with_brightness(&self, value: f32) -> HexColor<'static> {
+        let target = value.clamp(0.0, 1.0) * 255.0;
+        let (r, g, b) = self.get_rgb();
+        let max = r.max(g).max(b);
+        if max == 0 {
+            let gray = target.round() as u8;
+            return HexColor::new_rgb(gray, gray, gray);
+        }
+        let channel = |channel: u8| (channel as f32 * target / max as f32).round().clamp(0.0, 255.0) as u8;
+        HexColor::new_rgb(channel(r), channel(g), channel(b))
+    }
+
+    /// Hue in degrees `0.0..360.0`, saturation and lightness in `0.0..=1.0`. Grays have hue `0.0`
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let (hue, max, min) = hue_max_min(self.get_rgb());
+        let lightness = (max + min) / 2.0;
+        let saturation = match max == min {
+            true => 0.0,
+            false => (max - min) / (1.0 - (2.0 * lightness - 1.0).abs()),
+        };
+        (hue, saturation, lightness)
+    }
+
+    /// Inverse of [`HexColor::to_hsl`]. The hue wraps around, saturation and lightness are
+    /// clamped to `0.0..=1.0`
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> HexColor<'static> {
+        let (saturation, lightness) = (saturation.clamp(0.0, 1.0), lightness.clamp(0.0, 1.0));
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        from_hue_chroma(hue, chroma, lightness - chroma / 2.0)
+    }
+
+    /// Shifts the hue by `degrees`, keeping saturation and lightness
+    pub fn rotate_hue(&self, degrees: f32) -> HexColor<'static> {
+        let (hue, saturation, lightness) = self.to_hsl();
+        HexColor::from_hsl(hue + degrees, saturation, lightness)
+    }
+
+    /// Hue in degrees `0.0..360.0`, saturation and value in `0.0..=1.0`. Grays have hue `0.0`
+    pub fn to_hsv(&self) -> (f32, f32, f32) {
+        let (hue, max, min) = hue_max_min(self.get_rgb());
+        let saturation = match max {
+            0.0 => 0.0,
+            max => (max - min) / max,
+        };
+        (hue, saturation, max)
+    }
+
+    /// Inverse of [`HexColor::to_hsv`]. The hue wraps around, saturation and value are
+    /// clamped to `0.0..=1.0`
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> HexColor<'static> {
+        let (saturation, value) = (saturation.clamp(0.0, 1.0), value.clamp(0.0, 1.0));
+        let chroma = value * saturation;
+        from_hue_chroma(hue, chroma, value - chroma)
+    }
+
+    /// Finds the default color closest to this color in RGB space
+    pub fn nearest_default(&self) -> DefaultColor {
+        let (r, g, b) = self.get_rgb();
+        let distance = |color: &DefaultColor| {
+            let (dr, dg, db) = color.get_rgb();
+            let (dr, dg, db) = (r as i32 - dr as i32, g as i32 - dg as i32, b as i32 - db as i32);
+            dr * dr + dg * dg + db * db
+        };
+        // Safety. DefaultColor::ALL is not empty
+        unsafe { DefaultColor::ALL.into_iter().min_by_key(distance).unwrap_unchecked() }
+    }
+}
+
+/// Hue in degrees and the largest and smallest channel in `0.0..=1.0`
+fn hue_max_min((r, g, b): (u8, u8, u8)) -> (f32, f32, f32) {
+    let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+    let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+    let delta = max - min;
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    (hue, max, min)
+}
+
+/// Color with the given hue and chroma, with `offset` added to every channel
+fn from_hue_chroma(hue: f32, chroma: f32, offset: f32) -> HexColor<'static> {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match sector as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let channel = |channel: f32| ((channel + offset) * 255.0).round().clamp(0.0, 255.0) as u8;
+    HexColor::new_rgb(channel(r), channel(g), channel(b))
+}
+
+/// Color of the `index`th of `total` characters with the stops spread evenly over them.
+/// `stops` must not be empty
+pub(crate) fn gradient_color(stops: &[HexColor], index: usize, total: usize) -> HexColor<'static> {
+    if stops.len() == 1 {
+        let (r, g, b) = stops[0].get_rgb();
+        return HexColor::new_rgb(r, g, b);
+    }
+    let t = match total {
+        0 | 1 => 0.0,
+        total => index as f32 / (total - 1) as f32,
+    };
+    let scaled = t * (stops.len() - 1) as f32;
+    let segment = (scaled.floor() as usize).min(stops.len() - 2);
+    stops[segment].lerp(&stops[segment + 1], scaled - segment as f32)
+}
+
+impl DefaultColor {
+    pub const ALL: [DefaultColor; 16] = [
+        Self::Black,
+        Self::DarkBlue,
+        Self::DarkGreen,
+        Self::DarkCyan,
+        Self::DarkRed,
+        Self::Purple,
+        Self::Gold,
+        Self::Gray,
+        Self::DarkGray,
+        Self::Blue,
+        Self::BrightGreen,
+        Self::Cyan,
+        Self::Red,
+        Self::Pink,
+        Self::Yellow,
+        Self::White,
+    ];
+
+    /// The color vanilla renders this default color with
+    pub const fn get_rgb(&self) -> (u8, u8, u8) {
+        match self {
+            Self::Black => (0x00, 0x00, 0x00),
+            Self::DarkBlue => (0x00, 0x00, 0xAA),
+            Self::DarkGreen => (0x00, 0xAA, 0x00),
+            Self::DarkCyan => (0x00, 0xAA, 0xAA),
+            Self::DarkRed => (0xAA, 0x00, 0x00),
+            Self::Purple => (0xAA, 0x00, 0xAA),
+            Self::Gold => (0xFF, 0xAA, 0x00),
+            Self::Gray => (0xAA, 0xAA, 0xAA),
+            Self::DarkGray => (0x55, 0x55, 0x55),
+            Self::Blue => (0x55, 0x55, 0xFF),
+            Self::BrightGreen => (0x55, 0xFF, 0x55),
+            Self::Cyan => (0x55, 0xFF, 0xFF),
+            Self::Red => (0xFF, 0x55, 0x55),
+            Self::Pink => (0xFF, 0x55, 0xFF),
+            Self::Yellow => (0xFF, 0xFF, 0x55),
+            Self::White => (0xFF, 0xFF, 0xFF),
+        }
+    }
+
+    /// Name vanilla uses for this color
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Black => "black",
+            Self::DarkBlue => "dark_blue",
+            Self::DarkGreen => "dark_green",
+            Self::DarkCyan => "dark_aqua",
+            Self::DarkRed => "dark_red",
+            Self::Purple => "dark_purple",
+            Self::Gold => "gold",
+            Self::Gray => "gray",
+            Self::DarkGray => "dark_gray",
+            Self::Blue => "blue",
+            Self::BrightGreen => "green",
+            Self::Cyan => "aqua",
+            Self::Red => "red",
+            Self::Pink => "light_purple",
+            Self::Yellow => "yellow",
+            Self::White => "white",
+        }
+    }
+
+    /// Parses the name of a color ignoring case and underscores, so `"DARK_RED"` and `"darkRed"`
+    /// both work. Besides the vanilla names the variant names and `grey` are accepted
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name: String = name.chars()
+            .filter(|c| *c != '_')
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        let name = name.replace("grey", "gray");
+        Self::ALL.into_iter().find(|color| color.name().replace('_', "") == name).or(match name.as_str() {
+            "darkcyan" => Some(Self::DarkCyan),
+            "purple" => Some(Self::Purple),
+            "brightgreen" => Some(Self::BrightGreen),
+            "cyan" => Some(Self::Cyan),
+            "pink" => Some(Self::Pink),
+            _ => None,
+        })
+    }
+
+    /// Character following `§` to select this color in legacy formatted text
+    pub const fn legacy_code(&self) -> char {
+        b"0123456789abcdef"[*self as usize] as char
+    }
+
+    /// Parses a legacy formatting code, case insensitive
+    pub fn from_legacy_code(code: char) -> Option<Self> {
+        code.to_digit(16).map(|index| Self::ALL[index as usize])
+    }
+
+    pub const fn to_hex_color(&self) -> HexColor<'static> {
+        let (r, g, b) = self.get_rgb();
+        HexColor::new_rgb(r, g, b)
+    }
+}
+
+impl serde::Serialize for DefaultColor {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for DefaultColor {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = <Cow<str> as serde::Deserialize>::deserialize(deserializer)?;
+        let mut chars = name.chars();
+        if let (Some(code), None) = (chars.next(), chars.next()) {
+            return Self::from_legacy_code(code)
+                .ok_or_else(|| serde::de::Error::custom(format!("unknown legacy color code {:?}", code)));
+        }
+        Self::from_name(&name).ok_or_else(|| serde::de::Error::custom(format!("unknown color {:?}", name)))
+    }
+}
+
 impl Display for HexColor<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.get_hex())
     }
 }
 
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum HexColorRepr {
+    Hex(String),
+    Rgb([u8; 3]),
+}
+
+impl TryFrom<HexColorRepr> for HexColor<'_> {
+    type Error = HexColorError;
+
+    fn try_from(repr: HexColorRepr) -> Result<Self, Self::Error> {
+        match repr {
+            HexColorRepr::Hex(hex) => HexColor::new_hex(hex),
+            HexColorRepr::Rgb([r, g, b]) => Ok(HexColor::new_rgb(r, g, b)),
+        }
+    }
+}
+
+/// Serializes a [`HexColor`] as a `[r, g, b]` array, for use with `#[serde(with = "...")]`
+pub mod as_array {
+    use serde::{Deserialize, Serialize};
+    use super::HexColor;
+
+    pub fn serialize<S: serde::Serializer>(color: &HexColor, serializer: S) -> Result<S::Ok, S::Error> {
+        let (r, g, b) = color.get_rgb();
+        [r, g, b].serialize(serializer)
+    }
+
+    pub fn deserialize<'de, 'a, D: serde::Deserializer<'de>>(deserializer: D) -> Result<HexColor<'a>, D::Error> {
+        HexColor::deserialize(deserializer)
+    }
+}
+
+/// Serializes a [`HexColor`] as `#RRGGBB` with uppercase digits, for use with `#[serde(with = "...")]`
+pub mod as_upper_hex {
+    use serde::{Deserialize, Serialize};
+    use super::HexColor;
+
+    pub fn serialize<S: serde::Serializer>(color: &HexColor, serializer: S) -> Result<S::Ok, S::Error> {
+        color.to_hex_upper().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, 'a, D: serde::Deserializer<'de>>(deserializer: D) -> Result<HexColor<'a>, D::Error> {
+        HexColor::deserialize(deserializer)
+    }
+}
+
 impl TryFrom<String> for HexColor<'_> {
     type Error = HexColorError;
 
@@ -140,4 +510,118 @@ impl<'a> From<HexColor<'a>> for Color<'a> {
     fn from(hex_color: HexColor<'a>) -> Self {
         Color::Hex(hex_color)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_round_trip() {
+        for hex in ["#000000", "#ff5500", "#A0b1C2", "123abc"] {
+            let color = HexColor::new_hex(hex).unwrap();
+            let (r, g, b) = color.get_rgb();
+            assert_eq!(color.get_hex(), HexColor::new_rgb(r, g, b).get_hex());
+            assert_eq!(HexColor::new_hex(color.get_hex().into_owned()).unwrap().get_rgb(), (r, g, b));
+        }
+        assert_eq!(HexColor::new_hex("123abc").unwrap().get_rgb(), (0x12, 0x3a, 0xbc));
+        assert_eq!(HexColor::new_hex("A0B1C2").unwrap(), HexColor::new_hex("#a0b1c2").unwrap());
+        assert_eq!(HexColor::new_rgb(255, 0, 0), HexColor::new_hex("#ff0000").unwrap());
+        assert_ne!(HexColor::new_rgb(255, 0, 1), HexColor::new_hex("#ff0000").unwrap());
+        let hash = |color: &HexColor| {
+            use std::hash::{Hash, Hasher};
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            color.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&HexColor::new_rgb(1, 2, 3)), hash(&HexColor::new_hex("#010203").unwrap()));
+        assert_eq!(HexColor::new_hex("#12345"), Err(HexColorError::HexValueTooSmall));
+        assert_eq!(HexColor::new_hex("#1234567"), Err(HexColorError::HexValueTooLong));
+        assert_eq!(HexColor::new_hex("#12345g"), Err(HexColorError::HexValueContainsBadCharacters));
+        assert_eq!(HexColor::new_hex("1234567"), Err(HexColorError::HexValueTooLong));
+    }
+
+    #[test]
+    fn color_names() {
+        let parse = |json: &str| serde_json::from_str::<DefaultColor>(json).unwrap();
+        assert_eq!(parse(r#""GREY""#), DefaultColor::Gray);
+        assert_eq!(parse(r#""darkRed""#), DefaultColor::DarkRed);
+        assert_eq!(parse(r#""DARK_AQUA""#), DefaultColor::DarkCyan);
+        assert_eq!(parse(r#""cyan""#), DefaultColor::Cyan);
+        assert_eq!(parse(r#""aqua""#), DefaultColor::Cyan);
+        assert!(serde_json::from_str::<DefaultColor>(r#""blurple""#).is_err());
+        for color in DefaultColor::ALL {
+            let json = serde_json::to_string(&color).unwrap();
+            assert_eq!(json, format!("\"{}\"", color.name()));
+            assert_eq!(parse(&json), color);
+        }
+    }
+
+    #[test]
+    fn parse_color() {
+        assert_eq!(Color::parse("gold"), Ok(Color::Default(DefaultColor::Gold)));
+        assert_eq!(Color::parse("#ffaa00").unwrap(), Color::Hex(HexColor::new_hex("#ffaa00").unwrap()));
+        assert_eq!(Color::parse("#ffaa0"), Err(ColorParseError::Hex(HexColorError::HexValueTooSmall)));
+        assert_eq!(Color::parse("golden"), Err(ColorParseError::UnknownName("golden".to_string())));
+    }
+
+    #[test]
+    fn brightness() {
+        let white = HexColor::new_rgb(255, 255, 255);
+        assert_eq!(white.darken(0.5).get_rgb(), (128, 128, 128));
+        assert_eq!(white.darken(2.0).get_rgb(), (0, 0, 0));
+        assert_eq!(HexColor::new_rgb(0, 0, 0).lighten(0.5).get_rgb(), (128, 128, 128));
+        assert_eq!(HexColor::new_rgb(200, 100, 0).with_brightness(0.5).get_rgb(), (128, 64, 0));
+        assert_eq!(HexColor::new_rgb(0, 0, 0).with_brightness(1.0).get_rgb(), (255, 255, 255));
+    }
+
+    #[test]
+    fn rgb_array() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Config<'a> {
+            #[serde(with = "as_array")]
+            color: HexColor<'a>,
+        }
+
+        let color: HexColor = serde_json::from_str("[255, 170, 0]").unwrap();
+        assert_eq!(color.get_rgb(), (255, 170, 0));
+        assert_eq!(serde_json::to_string(&color).unwrap(), r##""#ffaa00""##);
+        assert!(serde_json::from_str::<HexColor>("[256, 0, 0]").is_err());
+        let config: Config = serde_json::from_str(r##"{"color":"#102030"}"##).unwrap();
+        assert_eq!(serde_json::to_string(&config).unwrap(), r#"{"color":[16,32,48]}"#);
+    }
+
+    #[test]
+    fn upper_hex() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Config<'a> {
+            #[serde(with = "as_upper_hex")]
+            color: HexColor<'a>,
+        }
+
+        assert_eq!(HexColor::new_rgb(255, 170, 0).to_hex_upper(), "#FFAA00");
+        assert_eq!(HexColor::new_hex("#ffaa00").unwrap().to_hex_upper(), "#FFAA00");
+        let config: Config = serde_json::from_str(r##"{"color":"#abcdef"}"##).unwrap();
+        assert_eq!(serde_json::to_string(&config).unwrap(), r##"{"color":"#ABCDEF"}"##);
+    }
+
+    #[test]
+    fn hsl_and_hsv() {
+        let close = |(a, b, c): (f32, f32, f32), (x, y, z): (f32, f32, f32)| {
+            (a - x).abs() < 0.5 && (b - y).abs() < 0.01 && (c - z).abs() < 0.01
+        };
+        assert!(close(HexColor::new_rgb(255, 0, 0).to_hsl(), (0.0, 1.0, 0.5)));
+        assert!(close(HexColor::new_rgb(0, 0, 255).to_hsv(), (240.0, 1.0, 1.0)));
+        assert!(close(HexColor::new_rgb(255, 170, 0).to_hsl(), (40.0, 1.0, 0.5)));
+        assert!(close(HexColor::new_rgb(128, 128, 128).to_hsl(), (0.0, 0.0, 0.502)));
+        assert_eq!(HexColor::from_hsl(120.0, 1.0, 0.5).get_rgb(), (0, 255, 0));
+        assert_eq!(HexColor::from_hsv(480.0, 1.0, 1.0).get_rgb(), (0, 255, 0));
+        for rgb in [(255, 170, 0), (16, 32, 48), (200, 10, 150), (0, 0, 0), (255, 255, 255), (85, 255, 255)] {
+            let color = HexColor::new_rgb(rgb.0, rgb.1, rgb.2);
+            let (h, s, l) = color.to_hsl();
+            assert_eq!(HexColor::from_hsl(h, s, l).get_rgb(), rgb);
+            let (h, s, v) = color.to_hsv();
+            assert_eq!(HexColor::from_hsv(h, s, v).get_rgb(), rgb);
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt::Write;
+use crate::component::{BaseComponent, ClickEvent, Component, HoverEvent};
+use crate::formatting::Color;
+use crate::sanitize::is_http_url;
+
+fn escape(html: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            c => html.push(c),
+        }
+    }
+}
+
+/// Renders the component as nested `<span>`s with inline styles. Styles are inherited like in
+/// vanilla. [`ClickEvent::OpenUrl`] with an `http` or `https` url becomes an `<a href>` around
+/// the component, other schemes like `javascript:` are never linked. [`HoverEvent::ShowText`]
+/// becomes the `title` of its span. All text is escaped
+pub fn to_html(component: &Component) -> String {
+    let mut html = String::new();
+    render(component, &BaseComponent::root_style(), &mut html);
+    html
+}
+
+fn render<'a>(component: &Component<'a>, parent: &BaseComponent<'a>, html: &mut String) {
+    let base = component.base();
+    let mut style = base.style();
+    style.inherit(parent);
+
+    let url = match &base.click_event {
+        Some(ClickEvent::OpenUrl(url)) if is_http_url(url) => Some(url),
+        _ => None,
+    };
+    if let Some(url) = url {
+        html.push_str("<a href=\"");
+        escape(html, url);
+        html.push_str("\">");
+    }
+
+    let mut css = String::new();
+    match &style.color {
+        Some(Color::Default(color)) => {
+            let (r, g, b) = color.get_rgb();
+            let _ = write!(css, "color:#{:02x}{:02x}{:02x};", r, g, b);
+        }
+        Some(Color::Hex(color)) => {
+            let _ = write!(css, "color:{};", color);
+        }
+        None => {}
+    }
+    if style.bold == Some(true) {
+        css.push_str("font-weight:bold;");
+    }
+    if style.italic == Some(true) {
+        css.push_str("font-style:italic;");
+    }
+    let decorations: Vec<_> = [(style.underlined, "underline"), (style.strikethrough, "line-through")]
+        .into_iter()
+        .filter(|(enabled, _)| *enabled == Some(true))
+        .map(|(_, decoration)| decoration)
+        .collect();
+    if !decorations.is_empty() {
+        let _ = write!(css, "text-decoration:{};", decorations.join(" "));
+    }
+
+    html.push_str("<span");
+    if !css.is_empty() {
+        html.push_str(" style=\"");
+        html.push_str(&css);
+        html.push('"');
+    }
+    if let Some(HoverEvent::ShowText(text)) = &base.hover_event {
+        html.push_str(" title=\"");
+        escape(html, &text.plain_text());
+        html.push('"');
+    }
+    html.push('>');
+    escape(html, &component.content());
+    component.children().for_each(|child| render(child, &style, html));
+    html.push_str("</span>");
+
+    if url.is_some() {
+        html.push_str("</a>");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use crate::component::TextComponent;
+    use crate::formatting::DefaultColor;
+    use super::*;
+
+    #[test]
+    fn escapes_text() {
+        assert_eq!(
+            to_html(&Component::from("<script>alert('x\" & y')</script>")),
+            "<span>&lt;script&gt;alert(&#39;x&quot; &amp; y&#39;)&lt;/script&gt;</span>"
+        );
+    }
+
+    #[test]
+    fn styles_and_links() {
+        let mut link = TextComponent::new("here");
+        link.base.underlined = Some(true);
+        link.base.click_event = Some(ClickEvent::OpenUrl(Cow::Borrowed("https://example.com/?a=1&b=2")));
+        link.base.hover_event = Some(HoverEvent::ShowText(Box::new("Open \"site\"".into())));
+        let mut root = TextComponent::new("Click ");
+        root.base.color = Some(DefaultColor::Red.into());
+        root.base.bold = Some(true);
+        assert_eq!(
+            to_html(&(root + link)),
+            "<span style=\"color:#ff5555;font-weight:bold;\">Click \
+            <a href=\"https://example.com/?a=1&amp;b=2\">\
+            <span style=\"color:#ff5555;font-weight:bold;text-decoration:underline;\" title=\"Open &quot;site&quot;\">here</span>\
+            </a></span>"
+        );
+    }
+
+    #[test]
+    fn unsafe_links() {
+        for url in ["javascript:alert(1)", "data:text/html,<script>alert(1)</script>", "JaVaScRiPt:alert(1)", " javascript://x"] {
+            let mut link = TextComponent::new("x");
+            link.base.click_event = Some(ClickEvent::OpenUrl(Cow::Borrowed(url)));
+            let html = to_html(&link.into());
+            assert!(!html.contains("href"), "{}", html);
+            assert_eq!(html, "<span>x</span>");
+        }
+        let mut link = TextComponent::new("x");
+        link.base.click_event = Some(ClickEvent::OpenUrl(Cow::Borrowed("HTTPS://example.com")));
+        assert_eq!(to_html(&link.into()), "<a href=\"HTTPS://example.com\"><span>x</span></a>");
+    }
+}
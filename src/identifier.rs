@@ -1,5 +1,5 @@
 use std::borrow::Cow;
-use std::fmt::{Display, Formatter};
+use std::fmt::{Display, Formatter, Write};
 use std::str::pattern::{Pattern, Searcher};
 
 #[derive(Clone, Debug)]
@@ -38,6 +38,9 @@ impl<'a> Identifier<'a> {
         self.0
     }
 
+    /// # Safety
+    /// The caller must guarantee that the inner value upholds the invariants checked by
+    /// [`Identifier::from_inner`]
     pub const unsafe fn from_inner_unchecked(inner: IdentifierInner<'a>) -> Self {
         Self::new(inner)
     }
@@ -93,12 +96,33 @@ impl<'a> Identifier<'a> {
 
     pub fn get_fulled(&'a self) -> Cow<'a, str> {
         match self.get_inner() {
-            IdentifierInner::Fulled(fulled) => Cow::Borrowed(&fulled),
+            IdentifierInner::Fulled(fulled) => Cow::Borrowed(fulled),
             IdentifierInner::Partial(key, value) =>
                 Cow::Owned(format!("{}:{}", key, value))
         }
     }
 
+    /// Writes the `namespace:path` form into `writer` without allocating an intermediate string
+    pub fn write_fulled(&self, writer: &mut impl Write) -> std::fmt::Result {
+        match self.get_inner() {
+            IdentifierInner::Fulled(fulled) => writer.write_str(fulled),
+            IdentifierInner::Partial(key, value) => {
+                writer.write_str(key)?;
+                writer.write_char(':')?;
+                writer.write_str(value)
+            }
+        }
+    }
+
+    /// Byte length of the `namespace:path` form, for preallocating buffers.
+    /// Can not be `const` since reading the length of a `Cow` goes through `Deref`
+    pub fn fulled_len(&self) -> usize {
+        match self.get_inner() {
+            IdentifierInner::Fulled(fulled) => fulled.len(),
+            IdentifierInner::Partial(key, value) => key.len() + 1 + value.len(),
+        }
+    }
+
     pub fn get_partial(&'a self) -> (&'a str, &'a str) {
         match self.get_inner() {
             IdentifierInner::Fulled(fulled) => {
@@ -106,7 +130,7 @@ impl<'a> Identifier<'a> {
                 let index = unsafe { fulled.find(':').unwrap_unchecked() };
                 (&fulled[0..index], &fulled[index + 1..fulled.len()])
             }
-            IdentifierInner::Partial(key, value) => (&key, &value)
+            IdentifierInner::Partial(key, value) => (key, value)
         }
     }
 
@@ -132,6 +156,72 @@ impl<'a> Identifier<'a> {
         }
     }
 
+    /// The namespace alone. A borrowed fulled identifier is sliced and an owned one reuses its
+    /// allocation, so unlike [`Identifier::into_partial`] nothing is allocated
+    pub fn into_namespace(self) -> Cow<'a, str> {
+        match self.into_inner() {
+            IdentifierInner::Fulled(fulled) => {
+                // Safety. guarantied by constructors
+                let index = unsafe { fulled.find(':').unwrap_unchecked() };
+                match fulled {
+                    Cow::Borrowed(fulled) => Cow::Borrowed(&fulled[..index]),
+                    Cow::Owned(mut fulled) => {
+                        fulled.truncate(index);
+                        Cow::Owned(fulled)
+                    }
+                }
+            }
+            IdentifierInner::Partial(key, _) => key,
+        }
+    }
+
+    /// The path alone, without allocating like [`Identifier::into_namespace`]
+    pub fn into_path(self) -> Cow<'a, str> {
+        match self.into_inner() {
+            IdentifierInner::Fulled(fulled) => {
+                // Safety. guarantied by constructors
+                let index = unsafe { fulled.find(':').unwrap_unchecked() };
+                match fulled {
+                    Cow::Borrowed(fulled) => Cow::Borrowed(&fulled[index + 1..]),
+                    Cow::Owned(mut fulled) => {
+                        fulled.drain(..=index);
+                        Cow::Owned(fulled)
+                    }
+                }
+            }
+            IdentifierInner::Partial(_, value) => value,
+        }
+    }
+
+    /// Replaces the namespace, keeping the path
+    pub fn with_namespace(self, namespace: impl Into<Cow<'a, str>>) -> Result<Self, IdentifierError> {
+        let path = self.into_path();
+        Self::new_partial(namespace, path)
+    }
+
+    /// Whether the namespace is `namespace`, without allocating
+    pub fn in_namespace(&self, namespace: &str) -> bool {
+        self.get_partial().0 == namespace
+    }
+
+    /// Converts into the fulled form, which every identifier has exactly one of. A partial
+    /// identifier is joined once, a fulled one is returned as is
+    pub fn canonicalize(self) -> Identifier<'a> {
+        match self.is_fulled() {
+            true => self,
+            false => Self::new(IdentifierInner::Fulled(self.into_fulled())),
+        }
+    }
+
+    /// Copies borrowed parts so the identifier no longer borrows anything
+    pub fn into_owned(self) -> Identifier<'static> {
+        Identifier::new(match self.into_inner() {
+            IdentifierInner::Fulled(fulled) => IdentifierInner::Fulled(Cow::Owned(fulled.into_owned())),
+            IdentifierInner::Partial(key, value) =>
+                IdentifierInner::Partial(Cow::Owned(key.into_owned()), Cow::Owned(value.into_owned())),
+        })
+    }
+
     pub const fn is_fulled(&self) -> bool {
         match self.get_inner() {
             IdentifierInner::Fulled(_) => true,
@@ -155,17 +245,43 @@ impl Display for IdentifierInner<'_> {
 
 impl Display for Identifier<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.get_inner())
+        self.write_fulled(f)
     }
 }
 
+/// Identifiers of the same form are compared directly, a fulled identifier borrowing the same
+/// string as the other one is equal without looking at the contents
 impl PartialEq for Identifier<'_> {
     fn eq(&self, other: &Self) -> bool {
-        self.get_partial() == other.get_partial()
+        match (&self.0, &other.0) {
+            (IdentifierInner::Fulled(fulled), IdentifierInner::Fulled(other)) =>
+                std::ptr::eq(fulled.as_ref(), other.as_ref()) || fulled == other,
+            (IdentifierInner::Partial(namespace, path), IdentifierInner::Partial(other_namespace, other_path)) =>
+                path == other_path && namespace == other_namespace,
+            _ => self.get_partial() == other.get_partial(),
+        }
+    }
+}
+
+impl Eq for Identifier<'_> {}
+
+/// Hashes the namespace and path, so both forms of an identifier hash the same
+impl std::hash::Hash for Identifier<'_> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.get_partial().hash(state)
     }
+}
+
+/// Compares against the `namespace:path` form without allocating
+impl PartialEq<str> for Identifier<'_> {
+    fn eq(&self, other: &str) -> bool {
+        other.split_once(':') == Some(self.get_partial())
+    }
+}
 
-    fn ne(&self, other: &Self) -> bool {
-        !self.eq(other)
+impl PartialEq<&str> for Identifier<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
     }
 }
 
@@ -248,4 +364,96 @@ mod tests {
             assert_eq!(identifier.get_partial(), ("minecraft", "grass_block"));
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn write_fulled() {
+        let mut buffer = String::from("id=");
+        Identifier::new_partial("minecraft", "stone").unwrap().write_fulled(&mut buffer).unwrap();
+        buffer.push(',');
+        Identifier::new_fulled("bird:chat").unwrap().write_fulled(&mut buffer).unwrap();
+        assert_eq!(buffer, "id=minecraft:stone,bird:chat");
+    }
+
+    #[test]
+    fn fulled_len() {
+        let partial = Identifier::new_partial("minecraft", "stone").unwrap();
+        assert_eq!(partial.fulled_len(), partial.get_fulled().len());
+        assert_eq!(Identifier::new_fulled("a:b").unwrap().fulled_len(), 3);
+    }
+
+    #[test]
+    fn eq_str() {
+        let fulled = Identifier::new_fulled("minecraft:stone").unwrap();
+        let partial = Identifier::new_partial("minecraft", "stone").unwrap();
+        assert!(fulled == "minecraft:stone");
+        assert!(partial == "minecraft:stone");
+        assert!(partial == *"minecraft:stone");
+        assert!(partial != "minecraft:stone:");
+        assert!(partial != "minecraft");
+        assert!(fulled != "other:stone");
+    }
+
+    #[test]
+    fn with_namespace() {
+        let remapped = Identifier::new_fulled("minecraft:stone").unwrap().with_namespace("custom").unwrap();
+        assert_eq!(remapped, Identifier::new_fulled("custom:stone").unwrap());
+        let remapped = Identifier::new_partial("minecraft", "stone").unwrap().with_namespace("custom").unwrap();
+        assert_eq!(remapped.to_string(), "custom:stone");
+        assert_eq!(
+            Identifier::new_fulled("minecraft:stone").unwrap().with_namespace("bad:"),
+            Err(IdentifierError::KeyContainsDoubleDot)
+        );
+    }
+
+    #[test]
+    fn eq_and_hash() {
+        use std::collections::HashSet;
+        let source = String::from("minecraft:stone");
+        let borrowed = Identifier::new_fulled(source.as_str()).unwrap();
+        assert_eq!(borrowed, Identifier::new_fulled(source.as_str()).unwrap());
+        assert_eq!(borrowed, Identifier::new_fulled("minecraft:stone").unwrap());
+        assert_eq!(borrowed, Identifier::new_partial("minecraft", "stone").unwrap());
+        assert_ne!(borrowed, Identifier::new_fulled("minecraft:dirt").unwrap());
+        assert_ne!(Identifier::new_partial("a", "b").unwrap(), Identifier::new_partial("b", "b").unwrap());
+        let set: HashSet<_> = [borrowed, Identifier::new_partial("minecraft", "stone").unwrap()].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn into_namespace_and_path() {
+        let borrowed = Identifier::new_fulled("minecraft:stone").unwrap();
+        assert!(matches!(borrowed.clone().into_namespace(), Cow::Borrowed("minecraft")));
+        assert!(matches!(borrowed.into_path(), Cow::Borrowed("stone")));
+
+        let owned = String::from("minecraft:stone");
+        let pointer = owned.as_ptr();
+        let path = Identifier::new_fulled(owned).unwrap().into_path();
+        assert_eq!(path, "stone");
+        assert!(matches!(&path, Cow::Owned(path) if path.as_ptr() == pointer));
+        assert_eq!(Identifier::new_fulled(String::from("a:b")).unwrap().into_namespace(), "a");
+
+        let partial = Identifier::new_partial("minecraft", String::from("dirt")).unwrap();
+        assert!(matches!(partial.clone().into_namespace(), Cow::Borrowed("minecraft")));
+        assert_eq!(partial.into_path(), "dirt");
+    }
+
+    #[test]
+    fn canonicalize() {
+        let fulled = Identifier::new_fulled("minecraft:stone").unwrap().canonicalize();
+        let partial = Identifier::new_partial("minecraft", "stone").unwrap().canonicalize();
+        assert!(fulled.is_fulled() && partial.is_fulled());
+        assert!(matches!(fulled.get_inner(), IdentifierInner::Fulled(Cow::Borrowed("minecraft:stone"))));
+        assert!(matches!(partial.get_inner(), IdentifierInner::Fulled(fulled) if fulled == "minecraft:stone"));
+    }
+
+    #[test]
+    fn in_namespace() {
+        let fulled = Identifier::new_fulled("minecraft:stone").unwrap();
+        assert!(fulled.in_namespace("minecraft"));
+        assert!(!fulled.in_namespace("other"));
+        assert!(!fulled.in_namespace("minecraft:stone"));
+        let partial = Identifier::new_partial("mymod", "gem").unwrap();
+        assert!(partial.in_namespace("mymod"));
+        assert!(!partial.in_namespace("minecraft"));
+    }
+}
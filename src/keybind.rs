@@ -0,0 +1,141 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use crate::component::{BaseComponent, KeyBindComponent};
+
+macro_rules! key_binds {
+    ($($variant:ident => $name:literal),* $(,)?) => {
+        /// Key binds of vanilla, rendered by the client as the key the player bound to them
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum KeyBind {
+            $($variant,)*
+        }
+
+        impl KeyBind {
+            pub const ALL: &'static [KeyBind] = &[$(Self::$variant,)*];
+
+            pub const fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)*
+                }
+            }
+        }
+    };
+}
+
+key_binds! {
+    Forward => "key.forward",
+    Left => "key.left",
+    Back => "key.back",
+    Right => "key.right",
+    Jump => "key.jump",
+    Sneak => "key.sneak",
+    Sprint => "key.sprint",
+    Inventory => "key.inventory",
+    SwapOffhand => "key.swapOffhand",
+    Drop => "key.drop",
+    Use => "key.use",
+    Attack => "key.attack",
+    PickItem => "key.pickItem",
+    Chat => "key.chat",
+    PlayerList => "key.playerlist",
+    Command => "key.command",
+    SocialInteractions => "key.socialInteractions",
+    Screenshot => "key.screenshot",
+    TogglePerspective => "key.togglePerspective",
+    SmoothCamera => "key.smoothCamera",
+    Fullscreen => "key.fullscreen",
+    SpectatorOutlines => "key.spectatorOutlines",
+    Advancements => "key.advancements",
+    Hotbar1 => "key.hotbar.1",
+    Hotbar2 => "key.hotbar.2",
+    Hotbar3 => "key.hotbar.3",
+    Hotbar4 => "key.hotbar.4",
+    Hotbar5 => "key.hotbar.5",
+    Hotbar6 => "key.hotbar.6",
+    Hotbar7 => "key.hotbar.7",
+    Hotbar8 => "key.hotbar.8",
+    Hotbar9 => "key.hotbar.9",
+    SaveToolbarActivator => "key.saveToolbarActivator",
+    LoadToolbarActivator => "key.loadToolbarActivator",
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown key bind {0:?}")]
+pub struct UnknownKeyBind(pub String);
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Key bind {0:?} is not of the form key.<name>")]
+pub struct InvalidKeyBind(pub String);
+
+impl Display for KeyBind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for KeyBind {
+    type Err = UnknownKeyBind;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::ALL.iter()
+            .find(|key_bind| key_bind.name() == name)
+            .copied()
+            .ok_or_else(|| UnknownKeyBind(name.to_string()))
+    }
+}
+
+impl<'a> KeyBindComponent<'a> {
+    pub fn new(key_bind: KeyBind) -> Self {
+        Self::from_raw(key_bind.name())
+    }
+
+    /// For key binds added by mods or not covered by [`KeyBind`]
+    pub fn from_raw(key_bind: impl Into<Cow<'a, str>>) -> Self {
+        Self { key_bind: key_bind.into(), base: BaseComponent::empty() }
+    }
+
+    /// Like [`KeyBindComponent::from_raw`], but rejects anything not following the `key.<name>`
+    /// convention of vanilla key binds and raw keys like `key.keyboard.a`
+    pub fn new_checked(key_bind: impl Into<Cow<'a, str>>) -> Result<Self, InvalidKeyBind> {
+        let key_bind = key_bind.into();
+        let valid = key_bind.strip_prefix("key.").is_some_and(|name| {
+            !name.is_empty() && name.split('.').all(|part| !part.is_empty() && !part.contains(char::is_whitespace))
+        });
+        match valid {
+            true => Ok(Self::from_raw(key_bind)),
+            false => Err(InvalidKeyBind(key_bind.into_owned())),
+        }
+    }
+
+    /// The key bind as a [`KeyBind`], if vanilla has it
+    pub fn known(&self) -> Option<KeyBind> {
+        self.key_bind.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jump() {
+        let component = KeyBindComponent::new(KeyBind::Jump);
+        assert_eq!(serde_json::to_string(&component).unwrap(), r#"{"keybind":"key.jump"}"#);
+        assert_eq!(component.known(), Some(KeyBind::Jump));
+        assert_eq!(KeyBindComponent::from_raw("key.mymod.dash").known(), None);
+        assert_eq!("key.hotbar.3".parse(), Ok(KeyBind::Hotbar3));
+        assert_eq!("jump".parse::<KeyBind>(), Err(UnknownKeyBind("jump".to_string())));
+        assert_eq!(KeyBind::Inventory.to_string(), "key.inventory");
+    }
+
+    #[test]
+    fn new_checked() {
+        assert_eq!(KeyBindComponent::new_checked("key.jump").unwrap(), KeyBindComponent::new(KeyBind::Jump));
+        assert!(KeyBindComponent::new_checked("key.keyboard.left.shift").is_ok());
+        assert_eq!(KeyBindComponent::new_checked("jump"), Err(InvalidKeyBind("jump".to_string())));
+        assert!(KeyBindComponent::new_checked("key.").is_err());
+        assert!(KeyBindComponent::new_checked("key..jump").is_err());
+        assert!(KeyBindComponent::new_checked("key.my jump").is_err());
+    }
+}
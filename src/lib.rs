@@ -2,4 +2,19 @@
 
 pub mod identifier;
 pub mod formatting;
-pub mod component;
\ No newline at end of file
+pub mod component;
+pub mod ansi;
+pub mod html;
+pub mod nbt;
+pub mod version;
+pub mod visitor;
+pub mod minimessage;
+pub mod sanitize;
+pub mod diff;
+pub mod owned;
+pub mod keybind;
+pub mod limits;
+pub mod validate;
+pub mod markdown;
+#[cfg(feature = "regex")]
+pub mod replace;
\ No newline at end of file
@@ -0,0 +1,132 @@
+use crate::component::{BoundedJsonError, Component, DepthError};
+
+/// Bounds for [`Component::from_json_limited`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonLimits {
+    /// Number of json values: objects, arrays, strings, numbers, booleans and nulls.
+    /// Object keys are not counted
+    pub max_nodes: usize,
+    /// Length of a single string or key in bytes, as written in the json with escapes
+    pub max_string_len: usize,
+    /// Nesting of objects and arrays
+    pub max_depth: usize,
+}
+
+impl Default for JsonLimits {
+    /// Generous limits for chat messages: vanilla caps a message at 262144 characters and
+    /// serde_json refuses to nest deeper than 128
+    fn default() -> Self {
+        Self { max_nodes: 65536, max_string_len: 262144, max_depth: 128 }
+    }
+}
+
+/// Checks the limits on the raw json before anything is allocated. Malformed json is left
+/// for serde_json to report
+fn check(json: &str, limits: &JsonLimits) -> Result<(), BoundedJsonError> {
+    // Whether every open container is an object
+    let mut containers = Vec::new();
+    let mut expect_key = false;
+    let mut nodes = 0;
+    let mut bytes = json.bytes().enumerate();
+    let count_node = |nodes: &mut usize| match *nodes == limits.max_nodes {
+        true => Err(BoundedJsonError::Nodes(limits.max_nodes)),
+        false => {
+            *nodes += 1;
+            Ok(())
+        }
+    };
+    while let Some((start, byte)) = bytes.next() {
+        match byte {
+            b'"' => {
+                let mut escaped = false;
+                let end = bytes.by_ref()
+                    .find(|(_, byte)| {
+                        let end = !escaped && *byte == b'"';
+                        escaped = !escaped && *byte == b'\\';
+                        end
+                    })
+                    .map_or(json.len(), |(end, _)| end);
+                if end - start - 1 > limits.max_string_len {
+                    return Err(BoundedJsonError::StringLength(limits.max_string_len));
+                }
+                if !expect_key {
+                    count_node(&mut nodes)?;
+                }
+                expect_key = false;
+            }
+            b'{' | b'[' => {
+                count_node(&mut nodes)?;
+                if containers.len() == limits.max_depth {
+                    return Err(DepthError { max: limits.max_depth }.into());
+                }
+                containers.push(byte == b'{');
+                expect_key = byte == b'{';
+            }
+            b'}' | b']' => {
+                containers.pop();
+                expect_key = false;
+            }
+            b',' => expect_key = containers.last() == Some(&true),
+            b':' => expect_key = false,
+            byte if byte.is_ascii_whitespace() => {}
+            _ => {
+                count_node(&mut nodes)?;
+                // Skip the rest of the number or literal
+                while let Some((_, byte)) = bytes.clone().next() {
+                    if matches!(byte, b',' | b'}' | b']' | b':' | b'"') || byte.is_ascii_whitespace() {
+                        break;
+                    }
+                    bytes.next();
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+impl Component<'_> {
+    /// Like [`Component::from_json`], but first checks the json against `limits` without
+    /// allocating, so untrusted input can not make deserialization use unbounded memory
+    pub fn from_json_limited(json: &str, limits: &JsonLimits) -> Result<Self, BoundedJsonError> {
+        check(json, limits)?;
+        Ok(Component::from_json(json)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_limit() {
+        let json = format!("[{}\"\"]", "\"a\",".repeat(1_000_000));
+        assert!(matches!(
+            Component::from_json_limited(&json, &JsonLimits::default()),
+            Err(BoundedJsonError::Nodes(65536))
+        ));
+        let json = r#"{"text":"a","extra":[{"text":"b","bold":true},"c"]}"#;
+        assert!(Component::from_json_limited(json, &JsonLimits { max_nodes: 7, ..JsonLimits::default() }).is_ok());
+        assert!(matches!(
+            Component::from_json_limited(json, &JsonLimits { max_nodes: 6, ..JsonLimits::default() }),
+            Err(BoundedJsonError::Nodes(6))
+        ));
+    }
+
+    #[test]
+    fn string_and_depth_limits() {
+        let limits = JsonLimits { max_string_len: 4, max_depth: 3, ..JsonLimits::default() };
+        assert!(Component::from_json_limited(r#"{"text":"a\"c"}"#, &limits).is_ok());
+        assert!(matches!(
+            Component::from_json_limited(r#"{"text":"abcde"}"#, &limits),
+            Err(BoundedJsonError::StringLength(4))
+        ));
+        assert!(matches!(
+            Component::from_json_limited(r#"[[[["a"]]]]"#, &limits),
+            Err(BoundedJsonError::Depth(DepthError { max: 3 }))
+        ));
+        assert!(matches!(
+            Component::from_json_limited(r#"{"text":"a""#, &limits),
+            Err(BoundedJsonError::Json(_))
+        ));
+    }
+}
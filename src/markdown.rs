@@ -0,0 +1,189 @@
+use std::borrow::Cow;
+use crate::component::{BaseComponent, ClickEvent, Component, TextComponent};
+use crate::sanitize::is_http_url;
+
+/// Delimiters in the order they are tried, longer ones first so `**` is not read as two `*`
+const DELIMITERS: [&str; 5] = ["**", "__", "~~", "*", "_"];
+
+fn apply(delimiter: &str, base: &mut BaseComponent) {
+    match delimiter {
+        "**" => base.bold = Some(true),
+        "__" => base.underlined = Some(true),
+        "~~" => base.strikethrough = Some(true),
+        _ => base.italic = Some(true),
+    }
+}
+
+/// Parses the Markdown subset used by chat applications: `**bold**`, `*italic*` or `_italic_`,
+/// `__underline__`, `~~strikethrough~~` and `[text](url)` links, which become underlined and open
+/// the url when clicked. Only `http` and `https` links are read, others are kept as text. Every
+/// span becomes a text component with its contents as children, so nested spans inherit the
+/// outer styles. Delimiters without a closing one are kept as text, `_` only opens a span at the
+/// start of a word and `\` escapes the next character
+pub fn from_markdown(input: &str) -> Component<'static> {
+    let mut parts = parse_inline(input);
+    match parts.len() {
+        0 => Component::empty(),
+        1 => parts.pop().expect("one part"),
+        _ => {
+            let mut root = TextComponent::new("");
+            root.base.add_extras(parts);
+            root.into()
+        }
+    }
+}
+
+fn parse_inline(input: &str) -> Vec<Component<'static>> {
+    let mut parts: Vec<Component<'static>> = Vec::new();
+    let mut text = String::new();
+    let mut rest = input;
+    while let Some(c) = rest.chars().next() {
+        if c == '\\' {
+            match rest[1..].chars().next() {
+                Some(escaped) if escaped.is_ascii_punctuation() => {
+                    text.push(escaped);
+                    rest = &rest[1 + escaped.len_utf8()..];
+                }
+                _ => {
+                    text.push('\\');
+                    rest = &rest[1..];
+                }
+            }
+            continue;
+        }
+        let word_start = !text.chars().last().is_some_and(char::is_alphanumeric);
+        let span = match c {
+            '[' => parse_link(rest),
+            _ => DELIMITERS.iter()
+                .filter(|delimiter| rest.starts_with(**delimiter) && (word_start || !delimiter.starts_with('_')))
+                .find_map(|delimiter| parse_span(rest, delimiter)),
+        };
+        match span {
+            Some((component, after)) => {
+                if !text.is_empty() {
+                    parts.push(TextComponent::new(std::mem::take(&mut text)).into());
+                }
+                parts.push(component);
+                rest = after;
+            }
+            None => {
+                text.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if !text.is_empty() {
+        parts.push(TextComponent::new(text).into());
+    }
+    parts
+}
+
+/// Text component holding the parsed `inner` markdown, its only text is stored directly
+fn styled(inner: &str, style: impl FnOnce(&mut BaseComponent<'static>)) -> Component<'static> {
+    let mut parts = parse_inline(inner);
+    let plain = matches!(parts.as_slice(), [Component::Text(text)] if text.base == BaseComponent::empty());
+    let mut component = match (plain, parts.pop()) {
+        (true, Some(Component::Text(text))) => text,
+        (_, last) => {
+            let mut component = TextComponent::new("");
+            component.base.add_extras(parts.into_iter().chain(last).collect::<Vec<_>>());
+            component
+        }
+    };
+    style(&mut component.base);
+    component.into()
+}
+
+/// Reads `delimiter`, the contents and the closing `delimiter`. The contents may not be empty
+fn parse_span<'i>(input: &'i str, delimiter: &str) -> Option<(Component<'static>, &'i str)> {
+    let inner = &input[delimiter.len()..];
+    let end = find_closing(inner, delimiter)?;
+    let after = &inner[end + delimiter.len()..];
+    if delimiter.starts_with('_') && after.chars().next().is_some_and(char::is_alphanumeric) {
+        return None;
+    }
+    Some((styled(&inner[..end], |base| apply(delimiter, base)), after))
+}
+
+/// Position of the closing `delimiter`, skipping escaped characters and, for single character
+/// delimiters, doubled ones which belong to a nested span
+fn find_closing(input: &str, delimiter: &str) -> Option<usize> {
+    let doubled = delimiter.repeat(2);
+    let mut index = 0;
+    while index < input.len() {
+        let rest = &input[index..];
+        if let Some(escaped) = rest.strip_prefix('\\') {
+            index += 1 + escaped.chars().next().map_or(0, char::len_utf8);
+        } else if delimiter.len() == 1 && rest.starts_with(doubled.as_str()) {
+            index += 2;
+        } else if rest.starts_with(delimiter) && index > 0 {
+            return Some(index);
+        } else {
+            index += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    None
+}
+
+/// Reads `[text](url)` with an `http` or `https` url
+fn parse_link(input: &str) -> Option<(Component<'static>, &str)> {
+    let inner = &input[1..];
+    let text_end = find_closing(inner, "]")?;
+    let url_part = inner[text_end + 1..].strip_prefix('(')?;
+    let url_end = url_part.find(')')?;
+    let url = &url_part[..url_end];
+    if !is_http_url(url) {
+        return None;
+    }
+    let component = styled(&inner[..text_end], |base| {
+        base.underlined = Some(true);
+        base.click_event = Some(ClickEvent::OpenUrl(Cow::Owned(url.to_string())));
+    });
+    Some((component, &url_part[url_end + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bold_link() {
+        let parsed = from_markdown("see **[docs](https://example.com)** now");
+        let extra = &parsed.base().extra;
+        assert_eq!(parsed.plain_text(), "see docs now");
+        assert_eq!(extra.len(), 3);
+        assert_eq!(extra[1].base().bold, Some(true));
+        let link = &extra[1].base().extra[0];
+        assert_eq!(link.content(), "docs");
+        assert_eq!(link.base().underlined, Some(true));
+        assert_eq!(link.base().click_event, Some(ClickEvent::OpenUrl(Cow::Borrowed("https://example.com"))));
+    }
+
+    #[test]
+    fn unsafe_links() {
+        for input in ["[x](javascript:alert(1))", "[x](JaVaScRiPt:alert(1))", "[x](data:text/html,hi)", "[x](file:///etc/passwd)"] {
+            let parsed = from_markdown(input);
+            assert!(parsed.click_events().is_empty(), "{}", input);
+            assert_eq!(parsed.plain_text(), input);
+        }
+        assert_eq!(from_markdown("[x](HTTP://example.com)").click_events().len(), 1);
+    }
+
+    #[test]
+    fn nested_and_literal() {
+        let parsed = from_markdown("**bold _and italic_**");
+        assert_eq!(parsed.base().bold, Some(true));
+        assert_eq!(parsed.base().extra[0], Component::from("bold "));
+        assert_eq!(parsed.base().extra[1].content(), "and italic");
+        assert_eq!(parsed.base().extra[1].base().italic, Some(true));
+
+        let mut struck = TextComponent::new("gone");
+        struck.base.strikethrough = Some(true);
+        assert_eq!(from_markdown("~~gone~~"), struck.into());
+        assert_eq!(from_markdown("__under__").base().underlined, Some(true));
+        assert_eq!(from_markdown("*it*").base().italic, Some(true));
+        assert_eq!(from_markdown("snake_case_name"), Component::from("snake_case_name"));
+        assert_eq!(from_markdown("2 * 3 = 6, \\*x\\*, **open"), Component::from("2 * 3 = 6, *x*, **open"));
+        assert_eq!(from_markdown("[no url]()"), Component::from("[no url]()"));
+    }
+}
@@ -0,0 +1,615 @@
+use std::borrow::Cow;
+use uuid::Uuid;
+use crate::component::{
+    BaseComponent, ClickEvent, Component, HoverEvent, KeyBindComponent, ScoreComponent, SelectorComponent, TextComponent,
+    TranslatableComponent,
+};
+use crate::formatting::{gradient_color, Color, DefaultColor, HexColor};
+use crate::identifier::Identifier;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MiniMessageError {
+    #[error("Closing tag </{0}> has no matching open tag")]
+    UnmatchedClosingTag(String),
+    #[error("Invalid color {0:?}")]
+    InvalidColor(String),
+    #[error("Invalid arguments for tag <{0}>")]
+    InvalidArguments(String),
+}
+
+enum Tag {
+    Open {
+        /// Name the closing tag has to use
+        name: String,
+        base: Box<BaseComponent<'static>>,
+        gradient: Option<Vec<HexColor<'static>>>,
+    },
+    Close(String),
+    /// Self-closing tag standing for a component of its own, like `<lang:...>`
+    Insert(Box<Component<'static>>),
+    Reset,
+    Newline,
+}
+
+struct Open {
+    name: String,
+    component: TextComponent<'static>,
+    gradient: Option<Vec<HexColor<'static>>>,
+}
+
+/// Parses MiniMessage markup. Supported tags are colors (`<red>`, `<#ff0000>`, `<color:red>`),
+/// decorations (`<bold>`, `<b>`, `<!italic>`, ...), `<font:id>`, `<insert:text>`,
+/// `<click:action:value>`, `<hover:show_text:'markup'>`, `<gradient:color:color...>`, `<reset>`
+/// and `<newline>`. `<lang:key:'markup'...>`, `<key:key>`, `<score:name:objective>` and
+/// `<selector:selector:'separator markup'>` stand for the matching component and have no
+/// closing tag. Every other tag becomes a nested text component with the rest of the markup up
+/// to its closing tag in `extra`, so styles are inherited. Closing a tag also closes the tags
+/// opened inside of it. Unknown tags are kept as text and `\<` escapes a literal `<`
+pub fn parse(input: &str) -> Result<Component<'static>, MiniMessageError> {
+    let mut root = parse_root(input)?;
+    Ok(match &mut root.base.extra {
+        Cow::Owned(extra) if root.text.is_empty() && extra.len() == 1 => extra.pop().expect("extra has one element"),
+        _ => root.into(),
+    })
+}
+
+fn parse_root(input: &str) -> Result<TextComponent<'static>, MiniMessageError> {
+    let mut stack = vec![Open { name: String::new(), component: TextComponent::new(""), gradient: None }];
+    let mut text = String::new();
+    let mut rest = input;
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '\\' => match rest[1..].chars().next() {
+                Some(escaped @ ('<' | '\\')) => {
+                    text.push(escaped);
+                    rest = &rest[2..];
+                }
+                _ => {
+                    text.push('\\');
+                    rest = &rest[1..];
+                }
+            },
+            '<' => match tag_end(rest).map(|end| (end, parse_tag(&rest[1..end]))) {
+                Some((end, tag)) => match tag? {
+                    Some(tag) => {
+                        flush_text(&mut stack, &mut text);
+                        apply_tag(&mut stack, &mut text, tag)?;
+                        rest = &rest[end + 1..];
+                    }
+                    None => {
+                        text.push_str(&rest[..=end]);
+                        rest = &rest[end + 1..];
+                    }
+                },
+                None => {
+                    text.push('<');
+                    rest = &rest[1..];
+                }
+            },
+            c => {
+                text.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    flush_text(&mut stack, &mut text);
+    while stack.len() > 1 {
+        close_top(&mut stack);
+    }
+    Ok(stack.pop().expect("root is never closed").component)
+}
+
+/// Index of the `>` closing the tag at the start of `input`, ignoring quoted arguments
+fn tag_end(input: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, c) in input.char_indices().skip(1) {
+        match (quote, c) {
+            (Some(_), _) if escaped => escaped = false,
+            (Some(_), '\\') => escaped = true,
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '>') => return Some(index),
+            (None, '<') => return None,
+            (None, _) => {}
+        }
+    }
+    None
+}
+
+/// Splits the tag on `:`, removing quotes around arguments
+fn split_args(tag: &str) -> Vec<String> {
+    let mut args = vec![String::new()];
+    let mut quote = None;
+    let mut chars = tag.chars();
+    while let Some(c) = chars.next() {
+        let arg = args.last_mut().expect("args are never empty");
+        match (quote, c) {
+            (Some(_), '\\') => match chars.next() {
+                Some(escaped) if Some(escaped) == quote || escaped == '\\' => arg.push(escaped),
+                Some(other) => {
+                    arg.push('\\');
+                    arg.push(other);
+                }
+                None => arg.push('\\'),
+            },
+            (Some(open), c) if c == open => quote = None,
+            (None, '\'' | '"') if arg.is_empty() => quote = Some(c),
+            (None, ':') => args.push(String::new()),
+            (_, c) => arg.push(c),
+        }
+    }
+    args
+}
+
+fn parse_color(color: &str) -> Result<Color<'static>, MiniMessageError> {
+    match color.starts_with('#') {
+        true => HexColor::new_hex(color.to_ascii_lowercase()).map(Color::Hex).ok(),
+        false => DefaultColor::from_name(&color.to_ascii_lowercase()).map(Color::Default),
+    }.ok_or_else(|| MiniMessageError::InvalidColor(color.to_string()))
+}
+
+fn decoration(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "bold" | "b" => "bold",
+        "italic" | "i" | "em" => "italic",
+        "underlined" | "u" => "underlined",
+        "strikethrough" | "st" => "strikethrough",
+        "obfuscated" | "obf" => "obfuscated",
+        _ => return None,
+    })
+}
+
+/// Canonical name of a known tag
+fn canonical_name(name: &str) -> Option<String> {
+    if let Some(decoration) = decoration(name) {
+        return Some(decoration.to_string());
+    }
+    match name {
+        "color" | "colour" | "c" => Some("color".to_string()),
+        "click" | "hover" | "gradient" | "reset" | "font" | "key" | "score" => Some(name.to_string()),
+        "insert" | "insertion" => Some("insert".to_string()),
+        "lang" | "tr" | "translate" => Some("lang".to_string()),
+        "selector" | "sel" => Some("selector".to_string()),
+        "newline" | "br" => Some("newline".to_string()),
+        name if name.starts_with('#') || DefaultColor::from_name(name).is_some() => Some(name.to_string()),
+        _ => None,
+    }
+}
+
+/// Parses the inside of `<...>`. Returns `None` for unknown tags
+fn parse_tag(tag: &str) -> Result<Option<Tag>, MiniMessageError> {
+    let (closing, tag) = match tag.strip_prefix('/') {
+        Some(tag) => (true, tag),
+        None => (false, tag),
+    };
+    let args = split_args(tag);
+    let name = args[0].to_ascii_lowercase();
+    let (negated, name) = match name.strip_prefix('!') {
+        Some(name) => (true, name.to_string()),
+        None => (false, name),
+    };
+    let name = match canonical_name(&name) {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+    if closing {
+        return Ok(Some(Tag::Close(name)));
+    }
+    let args = &args[1..];
+    let invalid = || MiniMessageError::InvalidArguments(name.clone());
+    let mut base = BaseComponent::empty();
+    let mut gradient = None;
+    match name.as_str() {
+        "bold" | "italic" | "underlined" | "strikethrough" | "obfuscated" => {
+            let value = match args.first().map(String::as_str) {
+                None => !negated,
+                Some("true") => !negated,
+                Some("false") => negated,
+                Some(_) => return Err(invalid()),
+            };
+            *match name.as_str() {
+                "bold" => &mut base.bold,
+                "italic" => &mut base.italic,
+                "underlined" => &mut base.underlined,
+                "strikethrough" => &mut base.strikethrough,
+                _ => &mut base.obfuscated,
+            } = Some(value);
+        }
+        "color" => base.color = Some(parse_color(args.first().ok_or_else(invalid)?)?),
+        "click" => {
+            let (action, value) = args.split_first().ok_or_else(invalid)?;
+            let joined = Cow::Owned(value.join(":"));
+            base.click_event = Some(match action.as_str() {
+                "open_url" => ClickEvent::OpenUrl(joined),
+                "run_command" => ClickEvent::RunCommand(joined),
+                "suggest_command" => ClickEvent::SuggestCommand(joined),
+                "change_page" => ClickEvent::ChangePage(joined.parse().map_err(|_| invalid())?),
+                "copy_to_clipboard" => ClickEvent::CopyToClipboard(joined),
+                "show_dialog" => ClickEvent::ShowDialog(identifier(joined).ok_or_else(invalid)?),
+                "custom" => {
+                    // The id is quoted when serialized, an unquoted one spans two arguments
+                    let (id, payload) = match value.first() {
+                        Some(id) if id.contains(':') => (id.clone(), &value[1..]),
+                        _ if value.len() >= 2 => (format!("{}:{}", value[0], value[1]), &value[2..]),
+                        _ => (value.join(":"), &[][..]),
+                    };
+                    ClickEvent::Custom {
+                        id: identifier(Cow::Owned(id)).ok_or_else(invalid)?,
+                        payload: match payload.is_empty() {
+                            true => None,
+                            false => Some(Cow::Owned(payload.join(":"))),
+                        },
+                    }
+                }
+                _ => return Err(invalid()),
+            });
+        }
+        "hover" => match args.split_first() {
+            Some((action, value)) if action == "show_text" => {
+                let text = parse_root(&value.join(":"))?;
+                base.hover_event = Some(HoverEvent::ShowText(Box::new(text.into())));
+            }
+            _ => return Err(invalid()),
+        },
+        "gradient" => {
+            let mut stops = args.iter()
+                .map(|color| parse_color(color).map(|color| match color {
+                    Color::Default(color) => color.to_hex_color(),
+                    Color::Hex(color) => color,
+                }))
+                .collect::<Result<Vec<_>, _>>()?;
+            match stops.len() {
+                0 => stops = vec![DefaultColor::White.to_hex_color(), DefaultColor::Black.to_hex_color()],
+                1 => return Err(invalid()),
+                _ => {}
+            }
+            gradient = Some(stops);
+        }
+        "font" => base.font = Some(identifier(Cow::Owned(args.join(":"))).ok_or_else(invalid)?),
+        "insert" => match args.is_empty() {
+            true => return Err(invalid()),
+            false => base.insertion = Some(Cow::Owned(args.join(":"))),
+        },
+        "lang" => {
+            let (key, args) = args.split_first().ok_or_else(invalid)?;
+            let mut translatable = TranslatableComponent::builder(key.clone()).build();
+            for arg in args {
+                translatable.add_arg(parse(arg)?);
+            }
+            return Ok(Some(Tag::Insert(Box::new(translatable.into()))));
+        }
+        "key" => match args {
+            [key] => return Ok(Some(Tag::Insert(Box::new(KeyBindComponent::from_raw(key.clone()).into())))),
+            _ => return Err(invalid()),
+        },
+        "score" => match args {
+            [name, objective] => {
+                let score = match Uuid::parse_str(name) {
+                    Ok(uuid) => ScoreComponent::builder(objective.clone()).entity(uuid),
+                    Err(_) => ScoreComponent::builder(objective.clone()).player(name.clone()),
+                };
+                return Ok(Some(Tag::Insert(Box::new(score.build().into()))));
+            }
+            _ => return Err(invalid()),
+        },
+        "selector" => match args {
+            [selector, separator @ ..] if separator.len() <= 1 => {
+                let selector = SelectorComponent {
+                    selector: Cow::Owned(selector.clone()),
+                    separator: separator.first().map(|separator| parse(separator).map(Box::new)).transpose()?,
+                    base: BaseComponent::empty(),
+                };
+                return Ok(Some(Tag::Insert(Box::new(selector.into()))));
+            }
+            _ => return Err(invalid()),
+        },
+        "reset" => return Ok(Some(Tag::Reset)),
+        "newline" => return Ok(Some(Tag::Newline)),
+        color => base.color = Some(parse_color(color)?),
+    }
+    Ok(Some(Tag::Open { name, base: Box::new(base), gradient }))
+}
+
+/// Identifiers without a namespace are in `minecraft`
+fn identifier(value: Cow<'static, str>) -> Option<Identifier<'static>> {
+    Identifier::new_with_default(value, "minecraft").ok()
+}
+
+fn apply_tag(stack: &mut Vec<Open>, text: &mut String, tag: Tag) -> Result<(), MiniMessageError> {
+    match tag {
+        Tag::Open { name, base, gradient } => stack.push(Open {
+            name,
+            component: TextComponent { text: Cow::Borrowed(""), base: *base },
+            gradient,
+        }),
+        Tag::Close(name) => {
+            let index = stack.iter().rposition(|open| open.name == name)
+                .filter(|index| *index != 0)
+                .ok_or(MiniMessageError::UnmatchedClosingTag(name))?;
+            while stack.len() > index {
+                close_top(stack);
+            }
+        }
+        Tag::Insert(component) => stack.last_mut().expect("root is never closed").component.base.add_extra(*component),
+        Tag::Reset => while stack.len() > 1 {
+            close_top(stack);
+        },
+        Tag::Newline => text.push('\n'),
+    }
+    Ok(())
+}
+
+/// Puts the text into the open component, as its own text if it has no children yet
+fn flush_text(stack: &mut [Open], text: &mut String) {
+    if text.is_empty() {
+        return;
+    }
+    let top = &mut stack.last_mut().expect("root is never closed").component;
+    let text = std::mem::take(text);
+    match top.text.is_empty() && top.base.extra.is_empty() {
+        true => top.text = Cow::Owned(text),
+        false => top.base.add_extra(TextComponent::new(text)),
+    }
+}
+
+fn close_top(stack: &mut Vec<Open>) {
+    let open = stack.pop().expect("root is never closed");
+    let mut component = Component::Text(open.component);
+    if let Some(stops) = open.gradient {
+        let total = gradient_len(&component);
+        apply_gradient(&mut component, &stops, &mut 0, total);
+    }
+    stack.last_mut().expect("root is never closed").component.base.add_extra(component);
+}
+
+/// Number of characters a gradient colors: text without its own color
+fn gradient_len(component: &Component) -> usize {
+    if component.base().color.is_some() {
+        return 0;
+    }
+    let own = match component {
+        Component::Text(text) => text.text.chars().count(),
+        _ => 0,
+    };
+    own + component.children().map(gradient_len).sum::<usize>()
+}
+
+/// Splits uncolored text into one child per character colored along the gradient
+fn apply_gradient(component: &mut Component<'static>, stops: &[HexColor], index: &mut usize, total: usize) {
+    if component.base().color.is_some() {
+        return;
+    }
+    if let Component::Text(text) = component {
+        if !text.text.is_empty() {
+            let chars: Vec<Component<'static>> = text.text.chars()
+                .map(|c| {
+                    let mut char = TextComponent::new(c.to_string());
+                    char.base.color = Some(gradient_color(stops, *index, total).into());
+                    *index += 1;
+                    char.into()
+                })
+                .collect();
+            text.text = Cow::Borrowed("");
+            let extra = std::mem::replace(&mut text.base.extra, Cow::Owned(chars));
+            text.base.add_extras(extra);
+        }
+    }
+    component.for_each_child_mut(|child| apply_gradient(child, stops, index, total));
+}
+
+/// Writes the component back as MiniMessage markup. Every styled property of a component
+/// becomes its own tag around the content and the children, so parsing the result gives back
+/// the tree [`parse`] produced. Translatable, key bind, score and selector components use the
+/// `<lang>`, `<key>`, `<score>` and `<selector>` tags. Hover events other than `show_text` have
+/// no tag and are left out
+pub fn serialize(component: &Component) -> String {
+    let mut out = String::new();
+    write_component(&mut out, component);
+    out
+}
+
+fn write_component(out: &mut String, component: &Component) {
+    let base = component.base();
+    let mut closing = Vec::new();
+    let mut open = |out: &mut String, tag: String, close: &str| {
+        out.push('<');
+        out.push_str(&tag);
+        out.push('>');
+        closing.push(close.to_string());
+    };
+    if let Some(color) = &base.color {
+        let color = match color {
+            Color::Default(color) => color.name().to_string(),
+            Color::Hex(color) => color.to_string(),
+        };
+        open(out, color.clone(), &color);
+    }
+    for (name, value) in [
+        ("bold", base.bold),
+        ("italic", base.italic),
+        ("underlined", base.underlined),
+        ("strikethrough", base.strikethrough),
+        ("obfuscated", base.obfuscated),
+    ] {
+        match value {
+            Some(true) => open(out, name.to_string(), name),
+            Some(false) => open(out, format!("!{}", name), name),
+            None => {}
+        }
+    }
+    if let Some(font) = &base.font {
+        open(out, format!("font:{}", quote(&font.to_string())), "font");
+    }
+    if let Some(insertion) = &base.insertion {
+        open(out, format!("insert:{}", quote(insertion)), "insert");
+    }
+    if let Some(click) = &base.click_event {
+        let (action, value) = match click {
+            ClickEvent::OpenUrl(url) => ("open_url", url.clone()),
+            ClickEvent::RunCommand(command) => ("run_command", command.clone()),
+            ClickEvent::SuggestCommand(command) => ("suggest_command", command.clone()),
+            ClickEvent::ChangePage(page) => ("change_page", Cow::Owned(page.to_string())),
+            ClickEvent::CopyToClipboard(text) => ("copy_to_clipboard", text.clone()),
+            ClickEvent::ShowDialog(dialog) => ("show_dialog", Cow::Owned(dialog.to_string())),
+            ClickEvent::Custom { id, payload } => ("custom", Cow::Owned(match payload {
+                Some(payload) => format!("{}:{}", quote(&id.to_string()), quote(payload)),
+                None => quote(&id.to_string()),
+            })),
+        };
+        let value = match click {
+            ClickEvent::Custom { .. } => value.into_owned(),
+            _ => quote(&value),
+        };
+        open(out, format!("click:{}:{}", action, value), "click");
+    }
+    if let Some(HoverEvent::ShowText(text)) = &base.hover_event {
+        open(out, format!("hover:show_text:{}", quote(&serialize(text))), "hover");
+    }
+    match component {
+        Component::Text(text) => out.push_str(&escape(&text.text)),
+        Component::Translatable(translatable) => {
+            out.push_str("<lang:");
+            out.push_str(&quote(&translatable.translate));
+            for arg in translatable.with.iter() {
+                out.push(':');
+                out.push_str(&quote(&serialize(arg)));
+            }
+            out.push('>');
+        }
+        Component::KeyBind(key_bind) => {
+            out.push_str(&format!("<key:{}>", quote(&key_bind.key_bind)));
+        }
+        Component::Score(score) => {
+            let name = match &score.score.name {
+                either::Either::Left(name) => name.to_string(),
+                either::Either::Right(uuid) => uuid.to_string(),
+            };
+            out.push_str(&format!("<score:{}:{}>", quote(&name), quote(&score.score.objective)));
+        }
+        Component::Selector(selector) => {
+            out.push_str("<selector:");
+            out.push_str(&quote(&selector.selector));
+            if let Some(separator) = &selector.separator {
+                out.push(':');
+                out.push_str(&quote(&serialize(separator)));
+            }
+            out.push('>');
+        }
+        Component::Base(_) => {}
+    }
+    base.extra.iter().for_each(|child| write_component(out, child));
+    for name in closing.into_iter().rev() {
+        out.push_str("</");
+        out.push_str(&name);
+        out.push('>');
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('<', "\\<")
+}
+
+fn quote(argument: &str) -> String {
+    format!("'{}'", argument.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested() {
+        let mut bold = TextComponent::new("there");
+        bold.base.bold = Some(true);
+        let mut red = TextComponent::new("hi ");
+        red.base.color = Some(DefaultColor::Red.into());
+        red.base.add_extra(bold);
+        assert_eq!(parse("<red>hi <bold>there</bold></red>").unwrap(), Component::Text(red));
+    }
+
+    #[test]
+    fn implicit_closing() {
+        let component = parse("a<b>b<i>c</b>d").unwrap();
+        let extra = &component.base().extra;
+        assert_eq!(component.content(), "a");
+        assert_eq!(extra.len(), 2);
+        assert_eq!(extra[0].base().bold, Some(true));
+        assert_eq!(extra[0].base().extra[0].base().italic, Some(true));
+        assert_eq!(extra[1], Component::from("d"));
+    }
+
+    #[test]
+    fn events() {
+        let component = parse("<click:open_url:https://example.com><hover:show_text:'<red>Open'>link").unwrap();
+        assert_eq!(component.base().click_event, Some(ClickEvent::OpenUrl(Cow::Borrowed("https://example.com"))));
+        let mut hover = TextComponent::new("");
+        hover.base.add_extra(TextComponent { base: BaseComponent { color: Some(DefaultColor::Red.into()), ..BaseComponent::empty() }, ..TextComponent::new("Open") });
+        assert_eq!(
+            component.base().extra[0].base().hover_event,
+            Some(HoverEvent::ShowText(Box::new(hover.into())))
+        );
+    }
+
+    #[test]
+    fn gradient() {
+        let component = parse("<gradient:#ff0000:#0000ff>abc</gradient>").unwrap();
+        let colors: Vec<_> = component.base().extra.iter()
+            .map(|char| match &char.base().color {
+                Some(Color::Hex(color)) => color.get_rgb(),
+                color => panic!("unexpected color {:?}", color),
+            })
+            .collect();
+        assert_eq!(colors, [(0xff, 0, 0), (0x80, 0, 0x80), (0, 0, 0xff)]);
+    }
+
+    #[test]
+    fn literals_and_errors() {
+        assert_eq!(parse("\\<red> <unknown> a < b").unwrap(), Component::from("<red> <unknown> a < b"));
+        assert_eq!(parse("hi</red>"), Err(MiniMessageError::UnmatchedClosingTag("red".to_string())));
+        assert_eq!(parse("<color:blurple>"), Err(MiniMessageError::InvalidColor("blurple".to_string())));
+        assert_eq!(parse("<click:explode:x>"), Err(MiniMessageError::InvalidArguments("click".to_string())));
+    }
+
+    #[test]
+    fn round_trip() {
+        let inputs = [
+            "<red>hi <bold>there</bold></red>!",
+            "a<!i>b<u>c</i>d \\<e\\\\",
+            "<click:run_command:'/say hi'><hover:show_text:'<#00ff00>it\\'s <b>bold'>press</click>",
+            "<color:gold><click:change_page:3>page</click> 3</color>",
+            "<gradient:red:blue>ab</gradient>",
+            "<font:uniform><insert:'a:b'>x<key:key.jump></insert></font>",
+            "<green><lang:chat.type.text:'<red>Steve':hi></green> <score:Steve:kills>",
+            "<score:'069a79f4-44e9-4726-a5be-fca90e38aaf5':deaths><selector:'@a':'<gray>, '>",
+            "<click:show_dialog:'ns:dialog'>a</click><click:custom:'ns:id':'{a:1}'>b</click><click:custom:ns:id>c",
+        ];
+        for input in inputs {
+            let component = parse(input).unwrap();
+            let serialized = serialize(&component);
+            assert_eq!(parse(&serialized).unwrap(), component, "{} -> {}", input, serialized);
+        }
+        assert_eq!(serialize(&parse("<red>hi <bold>there</bold></red>").unwrap()), "<red>hi <bold>there</bold></red>");
+        let font = parse(inputs[5]).unwrap();
+        assert_eq!(font.base().font, Some(Identifier::new_fulled("minecraft:uniform").unwrap()));
+        assert_eq!(font.base().extra[0].base().insertion.as_deref(), Some("a:b"));
+        assert!(matches!(&font.base().extra[0].base().extra[0], Component::KeyBind(key) if key.key_bind == "key.jump"));
+        let components = parse(inputs[6]).unwrap().iter()
+            .map(std::mem::discriminant)
+            .collect::<Vec<_>>();
+        for kind in [Component::from(TranslatableComponent::builder("").build()), ScoreComponent::builder("").build().into()] {
+            assert!(components.contains(&std::mem::discriminant(&kind)));
+        }
+        let selector = parse(inputs[7]).unwrap();
+        match selector.iter().find(|component| matches!(component, Component::Selector(_))) {
+            Some(Component::Selector(selector)) => assert_eq!(selector.separator.as_ref().unwrap().plain_text(), ", "),
+            component => panic!("unexpected component {:?}", component),
+        }
+        let custom = parse("<click:custom:'ns:id':'{a:1}'>b").unwrap();
+        assert_eq!(custom.base().click_event, Some(ClickEvent::Custom {
+            id: Identifier::new_fulled("ns:id").unwrap(),
+            payload: Some(Cow::Borrowed("{a:1}")),
+        }));
+    }
+}
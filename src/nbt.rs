@@ -0,0 +1,227 @@
+use std::fmt::{Display, Formatter, Write};
+use std::io;
+use crate::component::Component;
+
+/// NBT tag as used by the protocol to send components since 1.20.3
+#[derive(Debug, Clone, PartialEq)]
+pub enum Nbt {
+    Byte(i8),
+    Int(i32),
+    Long(i64),
+    Double(f64),
+    String(String),
+    List(Vec<Nbt>),
+    Compound(Vec<(String, Nbt)>),
+}
+
+impl Nbt {
+    const fn id(&self) -> u8 {
+        match self {
+            Self::Byte(_) => 1,
+            Self::Int(_) => 3,
+            Self::Long(_) => 4,
+            Self::Double(_) => 6,
+            Self::String(_) => 8,
+            Self::List(_) => 9,
+            Self::Compound(_) => 10,
+        }
+    }
+
+    /// Converts the json representation of a component. Booleans become bytes, `null`s are
+    /// skipped and lists with mixed element types have their elements wrapped into compounds
+    /// with an empty key, as vanilla does
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        Some(match value {
+            serde_json::Value::Null => return None,
+            serde_json::Value::Bool(value) => Self::Byte(*value as i8),
+            serde_json::Value::Number(number) => match number.as_i64() {
+                Some(number) => match i32::try_from(number) {
+                    Ok(number) => Self::Int(number),
+                    Err(_) => Self::Long(number),
+                },
+                None => Self::Double(number.as_f64().unwrap_or_default()),
+            },
+            serde_json::Value::String(string) => Self::String(string.clone()),
+            serde_json::Value::Array(array) => {
+                let mut list: Vec<_> = array.iter().filter_map(Self::from_json).collect();
+                if list.windows(2).any(|pair| pair[0].id() != pair[1].id()) {
+                    list = list.into_iter()
+                        .map(|element| match element {
+                            Self::Compound(compound) => Self::Compound(compound),
+                            element => Self::Compound(vec![(String::new(), element)]),
+                        })
+                        .collect();
+                }
+                Self::List(list)
+            }
+            serde_json::Value::Object(object) => Self::Compound(
+                object.iter()
+                    .filter_map(|(key, value)| Some((key.clone(), Self::from_json(value)?)))
+                    .collect()
+            ),
+        })
+    }
+
+    /// Formats the tag as SNBT
+    pub fn to_snbt(&self) -> String {
+        self.to_string()
+    }
+
+    /// Writes the tag in the network format used since 1.20.2: the tag id followed by the
+    /// payload, without the root name
+    pub fn write(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        writer.write_all(&[self.id()])?;
+        self.write_payload(writer)
+    }
+
+    fn write_payload(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        match self {
+            Self::Byte(value) => writer.write_all(&value.to_be_bytes()),
+            Self::Int(value) => writer.write_all(&value.to_be_bytes()),
+            Self::Long(value) => writer.write_all(&value.to_be_bytes()),
+            Self::Double(value) => writer.write_all(&value.to_be_bytes()),
+            Self::String(value) => write_string(writer, value),
+            Self::List(list) => {
+                writer.write_all(&[list.first().map_or(0, Self::id)])?;
+                let len = i32::try_from(list.len())
+                    .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "NBT list is too long"))?;
+                writer.write_all(&len.to_be_bytes())?;
+                list.iter().try_for_each(|element| element.write_payload(writer))
+            }
+            Self::Compound(compound) => {
+                for (key, value) in compound {
+                    writer.write_all(&[value.id()])?;
+                    write_string(writer, key)?;
+                    value.write_payload(writer)?;
+                }
+                writer.write_all(&[0])
+            }
+        }
+    }
+}
+
+/// Writes a string in java's modified UTF-8 prefixed by its length
+fn write_string(writer: &mut impl io::Write, value: &str) -> io::Result<()> {
+    let mut bytes = Vec::with_capacity(value.len());
+    for unit in value.encode_utf16() {
+        match unit {
+            0x01..=0x7F => bytes.push(unit as u8),
+            0x00 | 0x80..=0x7FF => bytes.extend_from_slice(&[
+                0xC0 | (unit >> 6) as u8,
+                0x80 | (unit & 0x3F) as u8,
+            ]),
+            _ => bytes.extend_from_slice(&[
+                0xE0 | (unit >> 12) as u8,
+                0x80 | ((unit >> 6) & 0x3F) as u8,
+                0x80 | (unit & 0x3F) as u8,
+            ]),
+        }
+    }
+    let len = u16::try_from(bytes.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "NBT string is too long"))?;
+    writer.write_all(&len.to_be_bytes())?;
+    writer.write_all(&bytes)
+}
+
+fn write_quoted(f: &mut Formatter<'_>, value: &str) -> std::fmt::Result {
+    f.write_char('"')?;
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            f.write_char('\\')?;
+        }
+        f.write_char(c)?;
+    }
+    f.write_char('"')
+}
+
+impl Display for Nbt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Byte(value) => write!(f, "{}b", value),
+            Self::Int(value) => write!(f, "{}", value),
+            Self::Long(value) => write!(f, "{}L", value),
+            Self::Double(value) => write!(f, "{}d", value),
+            Self::String(value) => write_quoted(f, value),
+            Self::List(list) => {
+                f.write_char('[')?;
+                for (index, element) in list.iter().enumerate() {
+                    if index != 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                f.write_char(']')
+            }
+            Self::Compound(compound) => {
+                f.write_char('{')?;
+                for (index, (key, value)) in compound.iter().enumerate() {
+                    if index != 0 {
+                        f.write_char(',')?;
+                    }
+                    match !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')) {
+                        true => f.write_str(key)?,
+                        false => write_quoted(f, key)?,
+                    }
+                    write!(f, ":{}", value)?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+impl Component<'_> {
+    /// Converts the component into the NBT form vanilla expects since 1.20.3
+    pub fn to_nbt(&self) -> Nbt {
+        Nbt::from_json(&serde_json::to_value(self).expect("components always serialize"))
+            .expect("components never serialize into null")
+    }
+
+    /// Writes [`Component::to_nbt`] in the network format, see [`Nbt::write`]
+    pub fn write_nbt<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.to_nbt().write(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use crate::component::{BaseComponent, TextComponent, TranslatableComponent};
+    use super::*;
+
+    #[test]
+    fn snbt() {
+        let mut arg = TextComponent { text: Cow::Borrowed("Steve"), base: BaseComponent::empty() };
+        arg.base.italic = Some(false);
+        let mut translatable = TranslatableComponent {
+            translate: Cow::Borrowed("chat.type.text"),
+            with: Cow::Borrowed(&[]),
+            base: BaseComponent::empty(),
+        };
+        translatable.add_arg(arg);
+        let mut component = TextComponent { text: Cow::Borrowed("hi \"you\""), base: BaseComponent::empty() };
+        component.base.bold = Some(true);
+        component.base.add_extra(translatable);
+        assert_eq!(
+            Component::from(component).to_nbt().to_snbt(),
+            r#"{bold:1b,extra:[{translate:"chat.type.text",with:[{italic:0b,text:"Steve"}]}],text:"hi \"you\""}"#
+        );
+    }
+
+    #[test]
+    fn network() {
+        let component = Component::from(TextComponent { text: Cow::Borrowed("hi"), base: BaseComponent::empty() });
+        let mut bytes = Vec::new();
+        component.to_nbt().write(&mut bytes).unwrap();
+        assert_eq!(bytes, [10, 8, 0, 4, b't', b'e', b'x', b't', 0, 2, b'h', b'i', 0]);
+        let mut written = Vec::new();
+        component.write_nbt(&mut written).unwrap();
+        assert_eq!(written, bytes);
+    }
+
+    #[test]
+    fn mixed_list() {
+        let list = Nbt::from_json(&serde_json::json!(["a", {"text": "b"}])).unwrap();
+        assert_eq!(list.to_snbt(), r#"[{"":"a"},{text:"b"}]"#);
+    }
+}
@@ -0,0 +1,244 @@
+//! Conversions of borrowed components into `'static` ones.
+//!
+//! With the `owned` feature this module also has aliases for the `'static` types and
+//! constructors which always allocate, for code that would rather not carry the lifetime
+//! around. The borrowed types stay the default: copying every string costs an allocation per
+//! field, which adds up when relaying many components that could borrow from the packet buffer.
+
+use std::borrow::Cow;
+use crate::component::{
+    BaseComponent, ClickEvent, Component, HoverEvent, KeyBindComponent, Score, ScoreComponent,
+    SelectorComponent, ShowEntityContents, ShowItemContents, TextComponent, TranslatableComponent,
+};
+
+fn owned_str(value: Cow<str>) -> Cow<'static, str> {
+    Cow::Owned(value.into_owned())
+}
+
+fn owned_list(list: Cow<[Component]>) -> Cow<'static, [Component<'static>]> {
+    match list.is_empty() {
+        true => Cow::Borrowed(&[]),
+        false => Cow::Owned(list.into_owned().into_iter().map(Component::into_owned).collect()),
+    }
+}
+
+impl Component<'_> {
+    /// Copies every borrowed part of the tree, including events, identifiers and colors,
+    /// so the component can outlive the data it was built from
+    pub fn into_owned(self) -> Component<'static> {
+        match self {
+            Self::Text(component) => Component::Text(component.into_owned()),
+            Self::Translatable(component) => Component::Translatable(component.into_owned()),
+            Self::KeyBind(component) => Component::KeyBind(component.into_owned()),
+            Self::Score(component) => Component::Score(component.into_owned()),
+            Self::Selector(component) => Component::Selector(component.into_owned()),
+            Self::Base(base) => Component::Base(base.into_owned()),
+        }
+    }
+}
+
+impl BaseComponent<'_> {
+    pub fn into_owned(self) -> BaseComponent<'static> {
+        BaseComponent {
+            bold: self.bold,
+            italic: self.italic,
+            underlined: self.underlined,
+            strikethrough: self.strikethrough,
+            obfuscated: self.obfuscated,
+            font: self.font.map(|font| font.into_owned()),
+            color: self.color.map(|color| color.into_owned()),
+            shadow_color: self.shadow_color,
+            insertion: self.insertion.map(owned_str),
+            extra: owned_list(self.extra),
+            click_event: self.click_event.map(ClickEvent::into_owned),
+            hover_event: self.hover_event.map(HoverEvent::into_owned),
+        }
+    }
+}
+
+impl TextComponent<'_> {
+    pub fn into_owned(self) -> TextComponent<'static> {
+        TextComponent { text: owned_str(self.text), base: self.base.into_owned() }
+    }
+}
+
+impl TranslatableComponent<'_> {
+    pub fn into_owned(self) -> TranslatableComponent<'static> {
+        TranslatableComponent {
+            translate: owned_str(self.translate),
+            with: owned_list(self.with),
+            base: self.base.into_owned(),
+        }
+    }
+}
+
+impl KeyBindComponent<'_> {
+    pub fn into_owned(self) -> KeyBindComponent<'static> {
+        KeyBindComponent { key_bind: owned_str(self.key_bind), base: self.base.into_owned() }
+    }
+}
+
+impl ScoreComponent<'_> {
+    pub fn into_owned(self) -> ScoreComponent<'static> {
+        ScoreComponent { score: self.score.into_owned(), base: self.base.into_owned() }
+    }
+}
+
+impl Score<'_> {
+    pub fn into_owned(self) -> Score<'static> {
+        Score {
+            name: self.name.map_left(owned_str),
+            objective: owned_str(self.objective),
+            value: self.value,
+        }
+    }
+}
+
+impl SelectorComponent<'_> {
+    pub fn into_owned(self) -> SelectorComponent<'static> {
+        SelectorComponent {
+            selector: owned_str(self.selector),
+            separator: self.separator.map(|separator| Box::new(separator.into_owned())),
+            base: self.base.into_owned(),
+        }
+    }
+}
+
+impl ClickEvent<'_> {
+    pub fn into_owned(self) -> ClickEvent<'static> {
+        match self {
+            Self::OpenUrl(url) => ClickEvent::OpenUrl(owned_str(url)),
+            Self::RunCommand(command) => ClickEvent::RunCommand(owned_str(command)),
+            Self::SuggestCommand(command) => ClickEvent::SuggestCommand(owned_str(command)),
+            Self::ChangePage(page) => ClickEvent::ChangePage(page),
+            Self::CopyToClipboard(text) => ClickEvent::CopyToClipboard(owned_str(text)),
+            Self::ShowDialog(dialog) => ClickEvent::ShowDialog(dialog.into_owned()),
+            Self::Custom { id, payload } => ClickEvent::Custom { id: id.into_owned(), payload: payload.map(owned_str) },
+        }
+    }
+}
+
+impl HoverEvent<'_> {
+    pub fn into_owned(self) -> HoverEvent<'static> {
+        match self {
+            Self::ShowText(text) => HoverEvent::ShowText(Box::new(text.into_owned())),
+            Self::ShowItem(item) => HoverEvent::ShowItem(item.map_either(ShowItemContents::into_owned, owned_str)),
+            Self::ShowEntity(entity) => HoverEvent::ShowEntity(entity.map_either(ShowEntityContents::into_owned, owned_str)),
+        }
+    }
+}
+
+impl ShowItemContents<'_> {
+    pub fn into_owned(self) -> ShowItemContents<'static> {
+        ShowItemContents { id: self.id.into_owned(), count: self.count, tag: self.tag.map(owned_str) }
+    }
+}
+
+impl ShowEntityContents<'_> {
+    pub fn into_owned(self) -> ShowEntityContents<'static> {
+        ShowEntityContents {
+            entity_type: self.entity_type.into_owned(),
+            id: self.id,
+            name: self.name.map(|name| Box::new(name.into_owned())),
+        }
+    }
+}
+
+#[cfg(feature = "owned")]
+pub type OwnedComponent = Component<'static>;
+#[cfg(feature = "owned")]
+pub type OwnedBaseComponent = BaseComponent<'static>;
+#[cfg(feature = "owned")]
+pub type OwnedTextComponent = TextComponent<'static>;
+#[cfg(feature = "owned")]
+pub type OwnedTranslatableComponent = TranslatableComponent<'static>;
+#[cfg(feature = "owned")]
+pub type OwnedKeyBindComponent = KeyBindComponent<'static>;
+#[cfg(feature = "owned")]
+pub type OwnedScoreComponent = ScoreComponent<'static>;
+#[cfg(feature = "owned")]
+pub type OwnedSelectorComponent = SelectorComponent<'static>;
+#[cfg(feature = "owned")]
+pub type OwnedClickEvent = ClickEvent<'static>;
+#[cfg(feature = "owned")]
+pub type OwnedHoverEvent = HoverEvent<'static>;
+#[cfg(feature = "owned")]
+pub type OwnedIdentifier = crate::identifier::Identifier<'static>;
+#[cfg(feature = "owned")]
+pub type OwnedColor = crate::formatting::Color<'static>;
+
+#[cfg(feature = "owned")]
+impl Component<'static> {
+    /// Parses json into a component owning all of its data
+    pub fn from_json_owned(json: &str) -> Result<Self, crate::component::ComponentError> {
+        Component::from_json(json).map(Component::into_owned)
+    }
+}
+
+#[cfg(feature = "owned")]
+impl TextComponent<'static> {
+    pub fn owned(text: impl Into<String>) -> Self {
+        TextComponent::new(text.into())
+    }
+}
+
+#[cfg(feature = "owned")]
+impl TranslatableComponent<'static> {
+    pub fn owned(translate: impl Into<String>) -> Self {
+        TranslatableComponent { translate: Cow::Owned(translate.into()), with: Cow::Borrowed(&[]), base: BaseComponent::empty() }
+    }
+}
+
+#[cfg(feature = "owned")]
+impl KeyBindComponent<'static> {
+    pub fn owned(key_bind: impl Into<String>) -> Self {
+        KeyBindComponent { key_bind: Cow::Owned(key_bind.into()), base: BaseComponent::empty() }
+    }
+}
+
+#[cfg(feature = "owned")]
+impl SelectorComponent<'static> {
+    pub fn owned(selector: impl Into<String>) -> Self {
+        SelectorComponent { selector: Cow::Owned(selector.into()), separator: None, base: BaseComponent::empty() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::Styled;
+    use crate::identifier::Identifier;
+    use super::*;
+
+    #[test]
+    fn outlives_source() {
+        let owned: Component<'static> = {
+            let source = String::from("hello https://example.com minecraft:uniform");
+            let json = format!(r#"{{"text":"{}"}}"#, source);
+            let mut component = Component::Text(TextComponent::new(source.as_str()))
+                .on_click_url(&source[6..25])
+                .with_font(Identifier::new_fulled(&source[26..]).unwrap())
+                .with_extra(Component::from_json(&json).unwrap());
+            component.base_mut().insertion = Some(Cow::Borrowed(&source[..5]));
+            component.into_owned()
+        };
+        assert_eq!(owned.content(), "hello https://example.com minecraft:uniform");
+        assert_eq!(owned.base().click_event, Some(ClickEvent::OpenUrl(Cow::Borrowed("https://example.com"))));
+        assert_eq!(owned.base().font.as_ref().unwrap(), "minecraft:uniform");
+        assert_eq!(owned.base().insertion.as_deref(), Some("hello"));
+        assert_eq!(owned.base().extra.len(), 1);
+    }
+
+    #[cfg(feature = "owned")]
+    #[test]
+    fn owned_aliases() {
+        fn store(component: OwnedComponent) -> Vec<OwnedComponent> {
+            vec![component]
+        }
+
+        let json = String::from(r#"{"text":"hi"}"#);
+        let stored = store(Component::from_json_owned(&json).unwrap());
+        drop(json);
+        assert_eq!(stored[0], Component::Text(OwnedTextComponent::owned("hi")));
+        assert_eq!(TranslatableComponent::owned("chat.type.text").translate, "chat.type.text");
+    }
+}
@@ -0,0 +1,87 @@
+//! Regex based replacement of text, behind the `regex` feature. Only the `text` of each text
+//! component is searched on its own, so a match spanning several components is not found
+
+use std::borrow::Cow;
+use regex::{Captures, Regex};
+use crate::component::{Component, TextComponent};
+
+impl<'a> Component<'a> {
+    /// Replaces every match of `pattern` in the text of every text component with `replacement`,
+    /// which may refer to capture groups like [`Regex::replace_all`]. The replaced text keeps the
+    /// style of its component
+    pub fn replace_text(&mut self, pattern: &Regex, replacement: &str) {
+        self.for_each_mut(|component| {
+            if let Component::Text(text) = component {
+                if let Cow::Owned(replaced) = pattern.replace_all(&text.text, replacement) {
+                    text.text = Cow::Owned(replaced);
+                }
+            }
+        });
+    }
+
+    /// Replaces every match of `pattern` with the component `replacement` returns, so the
+    /// replacement can have its own style. A text component with matches is split like
+    /// [`Component::linkify`] does: its text becomes empty and the pieces between the matches
+    /// and the replacements become its first children. Replacements are not searched again
+    pub fn replace_text_with(&mut self, pattern: &Regex, replacement: &mut impl FnMut(&Captures) -> Component<'a>) {
+        self.for_each_child_mut(|child| child.replace_text_with(pattern, replacement));
+        let text = match self {
+            Component::Text(text) if pattern.is_match(&text.text) => text,
+            _ => return,
+        };
+        let mut pieces: Vec<Component<'a>> = Vec::new();
+        let mut last = 0;
+        for captures in pattern.captures_iter(&text.text) {
+            let found = captures.get(0).expect("group 0 is the whole match");
+            if found.start() != last {
+                pieces.push(TextComponent::new(text.text[last..found.start()].to_string()).into());
+            }
+            pieces.push(replacement(&captures));
+            last = found.end();
+        }
+        if last != text.text.len() {
+            pieces.push(TextComponent::new(text.text[last..].to_string()).into());
+        }
+        text.text = Cow::Borrowed("");
+        let extra = std::mem::replace(&mut text.base.extra, Cow::Owned(pieces));
+        text.base.add_extras(extra);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::{BaseComponent, Styled};
+    use crate::formatting::DefaultColor;
+    use super::*;
+
+    fn message() -> Component<'static> {
+        let mut root = TextComponent::new("you dar");
+        root.base.color = Some(DefaultColor::Red.into());
+        root.base.add_extra(TextComponent::new("n fool, darn it"));
+        root.into()
+    }
+
+    #[test]
+    fn per_node() {
+        let mut censored = message();
+        censored.replace_text(&Regex::new("darn").unwrap(), "****");
+        assert_eq!(censored.plain_text(), "you darn fool, **** it");
+        assert_eq!(censored.base().color, Some(DefaultColor::Red.into()));
+        let mut swapped = Component::from("a=1 b=2");
+        swapped.replace_text(&Regex::new(r"(\w)=(\d)").unwrap(), "$2=$1");
+        assert_eq!(swapped.plain_text(), "1=a 2=b");
+    }
+
+    #[test]
+    fn styled_replacement() {
+        let mut censored = message();
+        censored.replace_text_with(&Regex::new("darn|fool").unwrap(), &mut |captures| {
+            TextComponent::new("*".repeat(captures[0].len())).with_color(DefaultColor::Gray).into()
+        });
+        assert_eq!(censored.plain_text(), "you darn ****, **** it");
+        let child = &censored.base().extra[0];
+        assert_eq!(child.content(), "");
+        assert_eq!(child.base().extra[1].base().color, Some(DefaultColor::Gray.into()));
+        assert_eq!(child.base().extra[0], Component::Text(TextComponent { text: "n ".into(), base: BaseComponent::empty() }));
+    }
+}
@@ -0,0 +1,150 @@
+use crate::component::{ClickEvent, Component, HoverEvent};
+
+/// What [`Component::sanitize`] keeps. The default policy removes every interaction
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SanitizePolicy {
+    pub allow_run_command: bool,
+    pub allow_suggest_command: bool,
+    pub allow_copy_to_clipboard: bool,
+    pub allow_change_page: bool,
+    /// Covers `show_dialog` and `custom` click events
+    pub allow_dialogs: bool,
+    pub allow_open_url: bool,
+    /// When set, only `http` and `https` links to these hosts or their subdomains are kept
+    pub allowed_hosts: Option<Vec<String>>,
+    pub allow_insertion: bool,
+    /// Components shown by kept hover events are sanitized with the same policy
+    pub allow_hover: bool,
+}
+
+impl SanitizePolicy {
+    fn allows_click(&self, click_event: &ClickEvent) -> bool {
+        match click_event {
+            ClickEvent::OpenUrl(url) => self.allow_open_url && match &self.allowed_hosts {
+                Some(hosts) => url_host(url).is_some_and(|host| hosts.iter().any(|allowed| {
+                    let allowed = allowed.to_ascii_lowercase();
+                    host == allowed || host.strip_suffix(&allowed).is_some_and(|sub| sub.ends_with('.'))
+                })),
+                None => true,
+            },
+            ClickEvent::RunCommand(_) => self.allow_run_command,
+            ClickEvent::SuggestCommand(_) => self.allow_suggest_command,
+            ClickEvent::ChangePage(_) => self.allow_change_page,
+            ClickEvent::CopyToClipboard(_) => self.allow_copy_to_clipboard,
+            ClickEvent::ShowDialog(_) | ClickEvent::Custom { .. } => self.allow_dialogs,
+        }
+    }
+}
+
+/// Whether the url starts with `http://` or `https://`, ignoring case. Other schemes like
+/// `javascript:` or `data:` must never end up in links rendered by a browser
+pub(crate) fn is_http_url(url: &str) -> bool {
+    url.split_once("://").is_some_and(|(scheme, _)| scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https"))
+}
+
+/// Lowercase host of an `http` or `https` url, without credentials and port. Urls containing
+/// `\`, whitespace or control characters have no host, browsers read them differently
+fn url_host(url: &str) -> Option<String> {
+    if !is_http_url(url) || url.contains(|c: char| c == '\\' || c.is_whitespace() || c.is_control()) {
+        return None;
+    }
+    let (_, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '\\', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?;
+    let host = match host.rsplit_once(':') {
+        Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => host,
+        _ => host,
+    };
+    match host.is_empty() {
+        true => None,
+        false => Some(host.to_ascii_lowercase()),
+    }
+}
+
+impl Component<'_> {
+    /// Removes every click event, hover event and insertion the policy does not allow from this
+    /// component and all of its descendants, including the components shown by hover events and
+    /// selector separators. Meant for relaying components written by players
+    pub fn sanitize(&mut self, policy: &SanitizePolicy) {
+        self.for_each_mut(|component| {
+            if let Component::Selector(selector) = component {
+                if let Some(separator) = &mut selector.separator {
+                    separator.sanitize(policy);
+                }
+            }
+            let base = component.base_mut();
+            if base.click_event.as_ref().is_some_and(|click_event| !policy.allows_click(click_event)) {
+                base.click_event = None;
+            }
+            if !policy.allow_insertion {
+                base.insertion = None;
+            }
+            match &mut base.hover_event {
+                Some(_) if !policy.allow_hover => base.hover_event = None,
+                Some(HoverEvent::ShowText(text)) => text.sanitize(policy),
+                Some(HoverEvent::ShowEntity(either::Either::Left(entity))) => {
+                    if let Some(name) = &mut entity.name {
+                        name.sanitize(policy);
+                    }
+                }
+                _ => {}
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::{BaseComponent, SelectorComponent, Styled, TextComponent};
+    use super::*;
+
+    #[test]
+    fn strips_disallowed() {
+        let hover = TextComponent::new("hi").on_click_command("/op me");
+        let mut component = TextComponent::new("a").on_click_command("/op me").on_hover_text(hover)
+            + TextComponent::new("b").on_click_url("https://docs.example.com/page")
+            + TextComponent::new("c").on_click_url("https://example.com.evil.net")
+            + TextComponent::new("d").on_click_url("file:///etc/passwd");
+        component.base_mut().insertion = Some("/op me".into());
+        let policy = SanitizePolicy {
+            allow_open_url: true,
+            allowed_hosts: Some(vec!["example.com".to_string()]),
+            allow_hover: true,
+            ..SanitizePolicy::default()
+        };
+        component.sanitize(&policy);
+        let base = component.base();
+        assert_eq!(base.click_event, None);
+        assert_eq!(base.insertion, None);
+        assert_eq!(base.hover_event, Some(HoverEvent::ShowText(Box::new("hi".into()))));
+        let links: Vec<_> = base.extra.iter().map(|child| child.base().click_event.is_some()).collect();
+        assert_eq!(links, [true, false, false]);
+    }
+
+    #[test]
+    fn separator() {
+        let separator = TextComponent::new(", ").on_click_command("/op me").on_hover_text("hi");
+        let mut component: Component = SelectorComponent {
+            selector: "@a".into(),
+            separator: Some(Box::new(separator.into())),
+            base: BaseComponent::empty(),
+        }.into();
+        component.sanitize(&SanitizePolicy::default());
+        let Component::Selector(selector) = &component else { unreachable!() };
+        let separator = selector.separator.as_ref().unwrap();
+        assert_eq!(separator.base().click_event, None);
+        assert_eq!(separator.base().hover_event, None);
+    }
+
+    #[test]
+    fn url_hosts() {
+        assert_eq!(url_host("HTTPS://user@Example.com:8080/x").as_deref(), Some("example.com"));
+        assert_eq!(url_host("http://example.com?q").as_deref(), Some("example.com"));
+        assert_eq!(url_host("ftp://example.com"), None);
+        assert_eq!(url_host("https://evil.net\\@example.com"), None);
+        assert_eq!(url_host("https://evil.net @example.com"), None);
+        assert_eq!(url_host("https://evil.net\t@example.com"), None);
+        let policy = SanitizePolicy { allow_open_url: true, allowed_hosts: Some(vec!["example.com".into()]), ..SanitizePolicy::default() };
+        assert!(!policy.allows_click(&ClickEvent::OpenUrl("https://evil.net\\@example.com".into())));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::component::{Component, HoverEvent};
+
+/// Structural rule a component breaks, see [`Component::validate`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{kind} at {path:?}")]
+pub struct ComponentValidationError {
+    /// How to reach the component from the root, empty for the root itself
+    pub path: Vec<PathSegment>,
+    pub kind: ValidationErrorKind,
+}
+
+/// One step from a component into a nested component
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathSegment {
+    /// Index into the children, translatable arguments before `extra`
+    Child(usize),
+    /// The text of [`HoverEvent::ShowText`]
+    HoverText,
+    /// The name of [`HoverEvent::ShowEntity`]
+    EntityName,
+    /// The separator of a selector component
+    Separator,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ValidationErrorKind {
+    #[error("Translatable component has an empty translation key")]
+    EmptyTranslate,
+    #[error("Key bind component has an empty key bind")]
+    EmptyKeyBind,
+    #[error("Score component has an empty name")]
+    EmptyScoreName,
+    #[error("Score component has an empty objective")]
+    EmptyScoreObjective,
+    #[error("Selector component has an empty selector")]
+    EmptySelector,
+}
+
+impl Component<'_> {
+    /// Checks the rules the client enforces on every component of the tree and reports all
+    /// violations in depth-first order. Hover texts, entity names and selector separators are
+    /// checked too, right after their component
+    pub fn validate(&self) -> Result<(), Vec<ComponentValidationError>> {
+        let mut errors = Vec::new();
+        validate_node(self, &mut Vec::new(), &mut errors);
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
+}
+
+fn validate_node(component: &Component, path: &mut Vec<PathSegment>, errors: &mut Vec<ComponentValidationError>) {
+    let mut report = |kind| errors.push(ComponentValidationError { path: path.clone(), kind });
+    match component {
+        Component::Translatable(translatable) if translatable.translate.is_empty() => report(ValidationErrorKind::EmptyTranslate),
+        Component::KeyBind(key_bind) if key_bind.key_bind.is_empty() => report(ValidationErrorKind::EmptyKeyBind),
+        Component::Score(score) => {
+            if score.score.name.as_ref().left().is_some_and(|name| name.is_empty()) {
+                report(ValidationErrorKind::EmptyScoreName);
+            }
+            if score.score.objective.is_empty() {
+                report(ValidationErrorKind::EmptyScoreObjective);
+            }
+        }
+        Component::Selector(selector) if selector.selector.is_empty() => report(ValidationErrorKind::EmptySelector),
+        _ => {}
+    }
+    let hover = match &component.base().hover_event {
+        Some(HoverEvent::ShowText(text)) => Some((PathSegment::HoverText, text.as_ref())),
+        Some(HoverEvent::ShowEntity(either::Either::Left(entity))) =>
+            entity.name.as_deref().map(|name| (PathSegment::EntityName, name)),
+        _ => None,
+    };
+    let separator = match component {
+        Component::Selector(selector) => selector.separator.as_deref().map(|separator| (PathSegment::Separator, separator)),
+        _ => None,
+    };
+    let children = component.children().enumerate().map(|(index, child)| (PathSegment::Child(index), child));
+    for (segment, nested) in hover.into_iter().chain(separator).chain(children) {
+        path.push(segment);
+        validate_node(nested, path, errors);
+        path.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::{BaseComponent, ScoreComponent, SelectorComponent, Styled, TextComponent, TranslatableComponent};
+    use super::*;
+
+    #[test]
+    fn missing_objective() {
+        let score = ScoreComponent::builder("").player("Steve").build();
+        let mut root = TextComponent::new("score: ");
+        root.base.add_extra(score);
+        let root = Component::from(root);
+        assert_eq!(
+            root.validate(),
+            Err(vec![ComponentValidationError { path: vec![PathSegment::Child(0)], kind: ValidationErrorKind::EmptyScoreObjective }])
+        );
+        assert_eq!(Component::from_json(r#"{"score":{"name":{"Left":""},"objective":""}}"#).unwrap().validate().unwrap_err().len(), 2);
+    }
+
+    #[test]
+    fn valid() {
+        let translatable = TranslatableComponent::builder("chat.type.text").arg("Steve").build();
+        assert_eq!(Component::from(translatable).validate(), Ok(()));
+        let empty = TranslatableComponent::builder("").arg(TranslatableComponent::builder("").build()).build();
+        let errors = Component::from(empty).validate().unwrap_err();
+        assert_eq!(errors.iter().map(|error| error.path.as_slice()).collect::<Vec<_>>(), [&[][..], &[PathSegment::Child(0)]]);
+    }
+
+    #[test]
+    fn nested_payloads() {
+        let bad_score = ScoreComponent::builder("").player("Steve").build();
+        let separator = TextComponent::new(", ").with_extra(TranslatableComponent::builder("").build());
+        let selector = SelectorComponent { selector: "@a".into(), separator: Some(Box::new(separator.into())), base: BaseComponent::empty() };
+        let root = Component::from(TextComponent::new("a").on_hover_text(bad_score).with_extra(selector));
+        let errors = root.validate().unwrap_err();
+        assert_eq!(errors, [
+            ComponentValidationError { path: vec![PathSegment::HoverText], kind: ValidationErrorKind::EmptyScoreObjective },
+            ComponentValidationError {
+                path: vec![PathSegment::Child(0), PathSegment::Separator, PathSegment::Child(0)],
+                kind: ValidationErrorKind::EmptyTranslate,
+            },
+        ]);
+    }
+}
@@ -0,0 +1,302 @@
+use std::borrow::Cow;
+use crate::component::{ClickEvent, Component, HoverEvent};
+use crate::formatting::Color;
+
+/// Protocol versions at which the json form of components changed.
+/// Each variant covers every release up to the next variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProtocolVersion {
+    /// 1.8 - 1.15.2. Hex colors are replaced with the nearest default color, `font` is dropped
+    /// and structured hover contents are converted into their legacy SNBT strings
+    V1_8,
+    /// 1.16 - 1.20.2. Hex colors, fonts and structured hover contents are supported
+    V1_16,
+    /// 1.20.3 - 1.21.3. Components are sent as NBT, see [`Component::to_nbt`].
+    /// Nothing is stripped compared to 1.16
+    V1_20_3,
+    /// 1.21.4. `shadow_color` is supported, older versions drop it
+    V1_21_4,
+    /// 1.21.5. Events are written as `click_event` and `hover_event` with their fields inline
+    /// instead of under `value` or `contents`, see [`Component::serialize_for`]
+    V1_21_5,
+    /// 1.21.6 and newer. `show_dialog` and `custom` click events are supported, older versions
+    /// drop them
+    V1_21_6,
+}
+
+impl ProtocolVersion {
+    pub const LATEST: Self = Self::V1_21_6;
+}
+
+impl<'a> Component<'a> {
+    /// Returns a copy of this component without anything the given version can not represent.
+    /// Hover texts, entity names and selector separators are converted too
+    pub fn for_version(&self, version: ProtocolVersion) -> Component<'a> {
+        let mut component = self.clone();
+        component.for_each_mut_including_events(|component| {
+            let base = component.base_mut();
+            if version < ProtocolVersion::V1_16 {
+                if let Some(Color::Hex(hex)) = &base.color {
+                    base.color = Some(hex.nearest_default().into());
+                }
+                base.font = None;
+                if let Some(hover_event) = &mut base.hover_event {
+                    legacy_hover_contents(hover_event);
+                }
+            }
+            if version < ProtocolVersion::V1_21_4 {
+                base.shadow_color = None;
+            }
+            if version < ProtocolVersion::V1_21_6 {
+                if let Some(ClickEvent::ShowDialog(_) | ClickEvent::Custom { .. }) = base.click_event {
+                    base.click_event = None;
+                }
+            }
+        });
+        component
+    }
+
+    /// Oldest version which can represent every part of this component, hover texts included.
+    /// [`Component::for_version`] returns the component unchanged for it and newer versions
+    pub fn min_protocol_version(&self) -> ProtocolVersion {
+        self.iter_including_events()
+            .map(|component| {
+                let base = component.base();
+                if let Some(ClickEvent::ShowDialog(_) | ClickEvent::Custom { .. }) = base.click_event {
+                    return ProtocolVersion::V1_21_6;
+                }
+                if base.shadow_color.is_some() {
+                    return ProtocolVersion::V1_21_4;
+                }
+                let structured_hover = matches!(
+                    base.hover_event,
+                    Some(HoverEvent::ShowItem(either::Either::Left(_)) | HoverEvent::ShowEntity(either::Either::Left(_)))
+                );
+                if matches!(base.color, Some(Color::Hex(_))) || base.font.is_some() || structured_hover {
+                    return ProtocolVersion::V1_16;
+                }
+                ProtocolVersion::V1_8
+            })
+            .max()
+            .unwrap_or(ProtocolVersion::V1_8)
+    }
+
+    /// Serializes this component like [`Component::to_json`], with every hex color replaced by
+    /// the nearest default color. Clients before 1.16 reject hex colors and show the text
+    /// unstyled. Hover texts, entity names and selector separators are converted too. Unlike
+    /// [`Component::serialize_for`] nothing else is changed
+    pub fn to_json_legacy_color(&self) -> Result<String, serde_json::Error> {
+        let mut component = self.clone();
+        component.for_each_mut_including_events(|component| {
+            let base = component.base_mut();
+            if let Some(Color::Hex(hex)) = &base.color {
+                base.color = Some(hex.nearest_default().into());
+            }
+        });
+        component.to_json()
+    }
+
+    /// Serializes this component into json understood by the given version. From 1.21.5 on the
+    /// events use the inline form, which has no place for item tags and legacy SNBT hover
+    /// contents: tags are left out and hover events holding SNBT strings are dropped
+    pub fn serialize_for(&self, version: ProtocolVersion) -> String {
+        let component = self.for_version(version);
+        if version < ProtocolVersion::V1_21_5 {
+            return serde_json::to_string(&component).expect("components always serialize");
+        }
+        let mut json = serde_json::to_value(&component).expect("components always serialize");
+        inline_events(&mut json);
+        json.to_string()
+    }
+}
+
+/// Rewrites `clickEvent` and `hoverEvent` of every component in `json` into the 1.21.5 form
+fn inline_events(json: &mut serde_json::Value) {
+    let mut stack = vec![json];
+    while let Some(value) = stack.pop() {
+        let object = match value {
+            serde_json::Value::Object(object) => object,
+            serde_json::Value::Array(array) => {
+                stack.extend(array);
+                continue;
+            }
+            _ => continue,
+        };
+        if let Some(mut click_event) = object.remove("clickEvent") {
+            if let Some(event) = click_event.as_object_mut() {
+                let field = match event.get("action").and_then(serde_json::Value::as_str) {
+                    Some("open_url") => Some("url"),
+                    Some("run_command" | "suggest_command") => Some("command"),
+                    Some("change_page") => Some("page"),
+                    _ => None,
+                };
+                if let (Some(field), Some(value)) = (field, event.remove("value")) {
+                    event.insert(field.to_string(), value);
+                }
+            }
+            object.insert("click_event".to_string(), click_event);
+        }
+        if let Some(mut hover_event) = object.remove("hoverEvent") {
+            let mut keep = true;
+            if let Some(event) = hover_event.as_object_mut() {
+                let action = event.get("action").and_then(serde_json::Value::as_str).unwrap_or_default().to_string();
+                if let Some(serde_json::Value::Object(contents)) = event.remove("contents") {
+                    for (key, value) in contents {
+                        let key = match (action.as_str(), key.as_str()) {
+                            ("show_item", "tag") => continue,
+                            ("show_entity", "type") => "id".to_string(),
+                            ("show_entity", "id") => "uuid".to_string(),
+                            _ => key,
+                        };
+                        event.insert(key, value);
+                    }
+                }
+                keep = action == "show_text" || !event.contains_key("value");
+            }
+            if keep {
+                object.insert("hover_event".to_string(), hover_event);
+            }
+        }
+        stack.extend(object.values_mut());
+    }
+}
+
+fn legacy_hover_contents(hover_event: &mut HoverEvent) {
+    match hover_event {
+        HoverEvent::ShowItem(either::Either::Left(item)) => {
+            let mut snbt = format!("{{id:\"{}\",Count:{}b", item.id, item.count.unwrap_or(1));
+            if let Some(tag) = &item.tag {
+                snbt.push_str(",tag:");
+                snbt.push_str(tag);
+            }
+            snbt.push('}');
+            *hover_event = HoverEvent::ShowItem(either::Either::Right(Cow::Owned(snbt)));
+        }
+        HoverEvent::ShowEntity(either::Either::Left(entity)) => {
+            let mut snbt = format!("{{type:\"{}\",id:\"{}\"", entity.entity_type, entity.id);
+            if let Some(name) = &entity.name {
+                // The name is written into the string before the walk could reach it
+                let name = name.serialize_for(ProtocolVersion::V1_8);
+                snbt.push_str(&format!(",name:{}", serde_json::Value::String(name)));
+            }
+            snbt.push('}');
+            *hover_event = HoverEvent::ShowEntity(either::Either::Right(Cow::Owned(snbt)));
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::{BaseComponent, ShowEntityContents, ShowItemContents, Styled, TextComponent};
+    use crate::formatting::{DefaultColor, HexColor, ShadowColor};
+    use crate::identifier::Identifier;
+    use super::*;
+
+    fn component() -> Component<'static> {
+        let mut base = BaseComponent::empty();
+        base.color = Some(HexColor::new_rgb(0xF0, 0x50, 0x50).into());
+        base.font = Some(Identifier::new_fulled("minecraft:uniform").unwrap());
+        base.shadow_color = Some(ShadowColor::Packed(0x7F000000));
+        base.click_event = Some(ClickEvent::ShowDialog(Identifier::new_fulled("minecraft:server_links").unwrap()));
+        base.hover_event = Some(HoverEvent::ShowItem(either::Either::Left(ShowItemContents {
+            id: Identifier::new_fulled("minecraft:diamond").unwrap(),
+            count: Some(2),
+            tag: None,
+        })));
+        TextComponent { text: Cow::Borrowed("hi"), base }.into()
+    }
+
+    #[test]
+    fn legacy() {
+        let legacy = component().for_version(ProtocolVersion::V1_8);
+        let base = legacy.base();
+        assert_eq!(base.color, Some(DefaultColor::Red.into()));
+        assert_eq!(base.font, None);
+        assert_eq!(base.click_event, None);
+        assert_eq!(
+            base.hover_event,
+            Some(HoverEvent::ShowItem(either::Either::Right(Cow::Borrowed("{id:\"minecraft:diamond\",Count:2b}"))))
+        );
+    }
+
+    #[test]
+    fn modern() {
+        let component = component();
+        let v1_16 = component.for_version(ProtocolVersion::V1_16);
+        assert_eq!(v1_16.base().font, component.base().font);
+        assert_eq!(v1_16.base().click_event, None);
+        assert_eq!(v1_16.base().shadow_color, None);
+        assert_eq!(component.for_version(ProtocolVersion::V1_21_4).base().shadow_color, component.base().shadow_color);
+        assert_eq!(component.for_version(ProtocolVersion::LATEST), component);
+        assert_eq!(
+            component.serialize_for(ProtocolVersion::V1_21_4),
+            serde_json::to_string(&component.for_version(ProtocolVersion::V1_21_4)).unwrap()
+        );
+        let json: serde_json::Value = serde_json::from_str(&component.serialize_for(ProtocolVersion::LATEST)).unwrap();
+        assert_eq!(json["click_event"], serde_json::json!({"action": "show_dialog", "dialog": "minecraft:server_links"}));
+        assert_eq!(json["hover_event"], serde_json::json!({"action": "show_item", "id": "minecraft:diamond", "count": 2}));
+        assert!(json.get("clickEvent").is_none() && json.get("hoverEvent").is_none());
+        assert_eq!(Component::from_json(&json.to_string()).unwrap(), component);
+    }
+
+    #[test]
+    fn inline_events() {
+        let entity = HoverEvent::ShowEntity(either::Either::Left(ShowEntityContents {
+            entity_type: Identifier::new_fulled("minecraft:pig").unwrap(),
+            id: uuid::Uuid::from_u128(1),
+            name: Some(Box::new(Component::from(TextComponent::new("Pig").on_click_command("/pig")))),
+        }));
+        let mut component = Component::from(TextComponent::new("a").on_click_url("https://example.com").on_hover(entity));
+        component.base_mut().add_extra(TextComponent::new("b").on_click(ClickEvent::ChangePage(3)));
+        let mut snbt = Component::from("c");
+        snbt.base_mut().hover_event = Some(HoverEvent::ShowItem(either::Either::Right(Cow::Borrowed("{id:\"minecraft:stone\"}"))));
+        component.base_mut().add_extra(snbt);
+        let json: serde_json::Value = serde_json::from_str(&component.serialize_for(ProtocolVersion::V1_21_5)).unwrap();
+        assert_eq!(json["click_event"], serde_json::json!({"action": "open_url", "url": "https://example.com"}));
+        assert_eq!(json["hover_event"], serde_json::json!({
+            "action": "show_entity",
+            "id": "minecraft:pig",
+            "uuid": "00000000-0000-0000-0000-000000000001",
+            "name": {"text": "Pig", "click_event": {"action": "run_command", "command": "/pig"}},
+        }));
+        assert_eq!(json["extra"][0]["click_event"], serde_json::json!({"action": "change_page", "page": 3}));
+        assert!(json["extra"][1].get("hover_event").is_none());
+        let parsed = Component::from_json(&json.to_string()).unwrap();
+        assert_eq!(parsed.base().hover_event, component.base().hover_event);
+        assert_eq!(parsed.base().extra[0], component.base().extra[0]);
+    }
+
+    #[test]
+    fn legacy_color() {
+        let mut component = Component::from("hi");
+        component.base_mut().color = Some(HexColor::new_rgb(0xF0, 0x50, 0x50).into());
+        let child = component.clone();
+        component.base_mut().add_extra(child);
+        assert_eq!(
+            component.to_json_legacy_color().unwrap(),
+            r#"{"text":"hi","color":"red","extra":[{"text":"hi","color":"red"}]}"#
+        );
+        let mut hover = Component::from("hover");
+        hover.base_mut().hover_event = Some(HoverEvent::ShowText(Box::new(component)));
+        assert!(!hover.to_json_legacy_color().unwrap().contains('#'));
+    }
+
+    #[test]
+    fn min_protocol_version() {
+        assert_eq!(Component::from("plain").min_protocol_version(), ProtocolVersion::V1_8);
+        let mut hex = Component::from("hex");
+        hex.base_mut().color = Some(HexColor::new_rgb(1, 2, 3).into());
+        assert_eq!(hex.min_protocol_version(), ProtocolVersion::V1_16);
+        let mut hover = Component::from("hover");
+        hover.base_mut().hover_event = Some(HoverEvent::ShowText(Box::new(hex)));
+        assert_eq!(hover.min_protocol_version(), ProtocolVersion::V1_16);
+        let legacy = hover.for_version(ProtocolVersion::V1_8);
+        assert_eq!(legacy.min_protocol_version(), ProtocolVersion::V1_8);
+        assert!(!hover.serialize_for(ProtocolVersion::V1_8).contains('#'));
+        assert_eq!(hover.for_version(ProtocolVersion::V1_16), hover);
+        assert_eq!(component().min_protocol_version(), ProtocolVersion::V1_21_6);
+        let version = component().min_protocol_version();
+        assert_eq!(component().for_version(version), component());
+    }
+}
@@ -0,0 +1,135 @@
+use crate::component::{
+    BaseComponent, Component, KeyBindComponent, ScoreComponent, SelectorComponent, TextComponent,
+    TranslatableComponent,
+};
+
+/// Mutable pass over a component tree. Every method recurses into the children by default,
+/// so an implementation only overrides the nodes it is interested in and calls the matching
+/// `walk_*` function if it still wants to descend.
+///
+/// ```
+/// use bird_chat::component::TextComponent;
+/// use bird_chat::visitor::{walk_text, ComponentVisitor};
+///
+/// struct Uppercase;
+///
+/// impl<'a> ComponentVisitor<'a> for Uppercase {
+///     fn visit_text(&mut self, component: &mut TextComponent<'a>) {
+///         component.text = component.text.to_uppercase().into();
+///         walk_text(self, component);
+///     }
+/// }
+///
+/// let mut component = TextComponent::new("hello ") + "world";
+/// component.accept(&mut Uppercase);
+/// assert_eq!(component, TextComponent::new("HELLO ") + "WORLD");
+/// ```
+pub trait ComponentVisitor<'a> {
+    fn visit_component(&mut self, component: &mut Component<'a>) {
+        walk_component(self, component)
+    }
+
+    fn visit_text(&mut self, component: &mut TextComponent<'a>) {
+        walk_text(self, component)
+    }
+
+    fn visit_translatable(&mut self, component: &mut TranslatableComponent<'a>) {
+        walk_translatable(self, component)
+    }
+
+    fn visit_key_bind(&mut self, component: &mut KeyBindComponent<'a>) {
+        walk_key_bind(self, component)
+    }
+
+    fn visit_score(&mut self, component: &mut ScoreComponent<'a>) {
+        walk_score(self, component)
+    }
+
+    fn visit_selector(&mut self, component: &mut SelectorComponent<'a>) {
+        walk_selector(self, component)
+    }
+
+    /// Called for the base of every component, including [`Component::Base`]
+    fn visit_base(&mut self, base: &mut BaseComponent<'a>) {
+        walk_base(self, base)
+    }
+}
+
+pub fn walk_component<'a, V: ComponentVisitor<'a> + ?Sized>(visitor: &mut V, component: &mut Component<'a>) {
+    match component {
+        Component::Text(component) => visitor.visit_text(component),
+        Component::Translatable(component) => visitor.visit_translatable(component),
+        Component::KeyBind(component) => visitor.visit_key_bind(component),
+        Component::Score(component) => visitor.visit_score(component),
+        Component::Selector(component) => visitor.visit_selector(component),
+        Component::Base(base) => visitor.visit_base(base),
+    }
+}
+
+pub fn walk_text<'a, V: ComponentVisitor<'a> + ?Sized>(visitor: &mut V, component: &mut TextComponent<'a>) {
+    visitor.visit_base(&mut component.base)
+}
+
+/// Visits the arguments before the base
+pub fn walk_translatable<'a, V: ComponentVisitor<'a> + ?Sized>(visitor: &mut V, component: &mut TranslatableComponent<'a>) {
+    if !component.with.is_empty() {
+        component.with.to_mut().iter_mut().for_each(|arg| visitor.visit_component(arg));
+    }
+    visitor.visit_base(&mut component.base)
+}
+
+pub fn walk_key_bind<'a, V: ComponentVisitor<'a> + ?Sized>(visitor: &mut V, component: &mut KeyBindComponent<'a>) {
+    visitor.visit_base(&mut component.base)
+}
+
+pub fn walk_score<'a, V: ComponentVisitor<'a> + ?Sized>(visitor: &mut V, component: &mut ScoreComponent<'a>) {
+    visitor.visit_base(&mut component.base)
+}
+
+pub fn walk_selector<'a, V: ComponentVisitor<'a> + ?Sized>(visitor: &mut V, component: &mut SelectorComponent<'a>) {
+    visitor.visit_base(&mut component.base)
+}
+
+/// Visits every `extra` child
+pub fn walk_base<'a, V: ComponentVisitor<'a> + ?Sized>(visitor: &mut V, base: &mut BaseComponent<'a>) {
+    if !base.extra.is_empty() {
+        base.extra.to_mut().iter_mut().for_each(|child| visitor.visit_component(child));
+    }
+}
+
+impl<'a> Component<'a> {
+    pub fn accept(&mut self, visitor: &mut impl ComponentVisitor<'a>) {
+        visitor.visit_component(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use super::*;
+
+    struct CountBases(usize);
+
+    impl<'a> ComponentVisitor<'a> for CountBases {
+        fn visit_base(&mut self, base: &mut BaseComponent<'a>) {
+            self.0 += 1;
+            walk_base(self, base)
+        }
+    }
+
+    #[test]
+    fn visits_arguments_and_extra() {
+        let mut translatable = TranslatableComponent {
+            translate: Cow::Borrowed("chat.type.text"),
+            with: Cow::Borrowed(&[]),
+            base: BaseComponent::empty(),
+        };
+        translatable.add_arg("Steve");
+        translatable.add_arg(TextComponent::new("hi") + "!");
+        let mut component = TextComponent::new("root") + translatable;
+        let mut visitor = CountBases(0);
+        component.accept(&mut visitor);
+        assert_eq!(visitor.0, component.iter().count());
+        assert_eq!(visitor.0, 5);
+    }
+}